@@ -0,0 +1,45 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use serde::{Serialize, Serializer};
+use serde_rusoto_dynamodb::OwnedBytes;
+
+// Bytes
+
+// Compare serializing a 1 MB binary value from a borrowed slice (which copies
+// the bytes in to the AttributeValue) with serializing it from OwnedBytes
+// (which hands off the existing buffer).
+
+const SIZE: usize = 1024 * 1024;
+
+struct BorrowedBytes<'a>(&'a [u8]);
+
+impl<'a> Serialize for BorrowedBytes<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+fn serialize_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize_bytes");
+
+    group.bench_function("copy", |b| {
+        let bytes = vec![0u8; SIZE];
+
+        b.iter(|| serde_rusoto_dynamodb::to_attribute_value(BorrowedBytes(&bytes)))
+    });
+
+    group.bench_function("move", |b| {
+        b.iter_batched(
+            || OwnedBytes::from(vec![0u8; SIZE]),
+            serde_rusoto_dynamodb::to_attribute_value,
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, serialize_bytes);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde_rusoto_dynamodb::{FromAttributeValue, ToAttributeValue};
+use std::hint::black_box;
+
+// Convert
+
+// Compare converting scalar values directly (through the ToAttributeValue and
+// FromAttributeValue traits) with serializing and deserializing them through
+// Serde.
+
+fn convert_i64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("convert_i64");
+    let value = 1_234_567_890i64;
+    let converted = value.to_attribute_value().unwrap();
+
+    group.bench_function("to_direct", |b| {
+        b.iter(|| black_box(value).to_attribute_value())
+    });
+
+    group.bench_function("to_serde", |b| {
+        b.iter(|| serde_rusoto_dynamodb::to_attribute_value(black_box(value)))
+    });
+
+    group.bench_function("from_direct", |b| {
+        b.iter(|| i64::from_attribute_value(black_box(&converted)))
+    });
+
+    group.bench_function("from_serde", |b| {
+        b.iter(|| serde_rusoto_dynamodb::from_attribute_value::<i64>(black_box(&converted)))
+    });
+
+    group.finish();
+}
+
+fn convert_string(c: &mut Criterion) {
+    let mut group = c.benchmark_group("convert_string");
+    let value = "hello world".to_owned();
+    let converted = value.to_attribute_value().unwrap();
+
+    group.bench_function("to_direct", |b| {
+        b.iter(|| black_box(&value).to_attribute_value())
+    });
+
+    group.bench_function("to_serde", |b| {
+        b.iter(|| serde_rusoto_dynamodb::to_attribute_value(black_box(&value)))
+    });
+
+    group.bench_function("from_direct", |b| {
+        b.iter(|| String::from_attribute_value(black_box(&converted)))
+    });
+
+    group.bench_function("from_serde", |b| {
+        b.iter(|| serde_rusoto_dynamodb::from_attribute_value::<String>(black_box(&converted)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, convert_i64, convert_string);
+criterion_main!(benches);
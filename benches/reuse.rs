@@ -0,0 +1,74 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rusoto_dynamodb::AttributeValue;
+use serde::Deserialize;
+use serde_rusoto_dynamodb::{de::AttributeValueDeserializer, DeserializerConfig};
+use std::hint::black_box;
+
+// Reuse
+
+// Compare deserializing a large page (100,000 small items) with a new
+// deserializer for each item against resetting a single deserializer.
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct Small {
+    id: String,
+    count: u32,
+}
+
+fn deserialize_page(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize_page");
+    let config = DeserializerConfig::default();
+    let values: Vec<AttributeValue> = (0..100_000)
+        .map(|i| AttributeValue {
+            m: Some(
+                vec![
+                    (
+                        "id".to_owned(),
+                        AttributeValue {
+                            s: Some(format!("item{}", i)),
+                            ..AttributeValue::default()
+                        },
+                    ),
+                    (
+                        "count".to_owned(),
+                        AttributeValue {
+                            n: Some(i.to_string()),
+                            ..AttributeValue::default()
+                        },
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            ..AttributeValue::default()
+        })
+        .collect();
+
+    group.bench_function("new", |b| {
+        b.iter(|| {
+            for value in black_box(&values) {
+                black_box(serde_rusoto_dynamodb::from_attribute_value_with_config::<
+                    Small,
+                >(value, &config))
+                .unwrap();
+            }
+        })
+    });
+
+    group.bench_function("reset", |b| {
+        b.iter(|| {
+            let mut deserializer = AttributeValueDeserializer::new(&values[0], &config);
+
+            for value in black_box(&values) {
+                deserializer.reset(value);
+                black_box(deserializer.deserialize::<Small>()).unwrap();
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, deserialize_page);
+criterion_main!(benches);
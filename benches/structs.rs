@@ -0,0 +1,103 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rusoto_dynamodb::AttributeValue;
+use serde::Deserialize;
+
+// Structs
+
+// Measure deserializing a wide (50 field) struct from a map value, the case in
+// which matching each key of the map against the expected fields would be most
+// costly, and deserializing a list of enums, where each variant name is
+// matched as an identifier.
+
+macro_rules! wide {
+    ($($field:ident),*) => {
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct Wide {
+            $($field: u32,)*
+        }
+
+        const FIELDS: &[&str] = &[$(stringify!($field)),*];
+    };
+}
+
+wide!(
+    f00, f01, f02, f03, f04, f05, f06, f07, f08, f09, f10, f11, f12, f13, f14, f15, f16, f17, f18,
+    f19, f20, f21, f22, f23, f24, f25, f26, f27, f28, f29, f30, f31, f32, f33, f34, f35, f36, f37,
+    f38, f39, f40, f41, f42, f43, f44, f45, f46, f47, f48, f49
+);
+
+fn deserialize_struct(c: &mut Criterion) {
+    let value = AttributeValue {
+        m: Some(
+            FIELDS
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let value = AttributeValue {
+                        n: Some(i.to_string()),
+                        ..AttributeValue::default()
+                    };
+
+                    (field.to_string(), value)
+                })
+                .collect(),
+        ),
+        ..AttributeValue::default()
+    };
+
+    c.bench_function("deserialize_struct", |b| {
+        b.iter(|| serde_rusoto_dynamodb::from_attribute_value::<Wide>(&value))
+    });
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+enum Event {
+    Created { id: u32 },
+    Deleted { id: u32 },
+    Renamed { id: u32 },
+    Unchanged,
+}
+
+fn deserialize_enum_list(c: &mut Criterion) {
+    let value = AttributeValue {
+        l: Some(
+            ["Created", "Deleted", "Renamed"]
+                .iter()
+                .cycle()
+                .take(1_000)
+                .enumerate()
+                .map(|(i, variant)| {
+                    let fields = AttributeValue {
+                        m: Some(
+                            vec![(
+                                "id".to_owned(),
+                                AttributeValue {
+                                    n: Some(i.to_string()),
+                                    ..AttributeValue::default()
+                                },
+                            )]
+                            .into_iter()
+                            .collect(),
+                        ),
+                        ..AttributeValue::default()
+                    };
+
+                    AttributeValue {
+                        m: Some(vec![(variant.to_string(), fields)].into_iter().collect()),
+                        ..AttributeValue::default()
+                    }
+                })
+                .collect(),
+        ),
+        ..AttributeValue::default()
+    };
+
+    c.bench_function("deserialize_enum_list", |b| {
+        b.iter(|| serde_rusoto_dynamodb::from_attribute_value::<Vec<Event>>(&value))
+    });
+}
+
+criterion_group!(benches, deserialize_struct, deserialize_enum_list);
+criterion_main!(benches);
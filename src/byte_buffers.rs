@@ -0,0 +1,108 @@
+// Byte Buffers
+
+// Helpers for use with serde(with) to store the buffer types of the bytes crate
+// (Bytes and BytesMut, from version 1 of the crate, which differs from the
+// version used by Rusoto) as the native AWS AttributeValue binary type,
+// available with the "bytes" feature. Values are serialized as bytes (which
+// are copied in to the binary value once), and deserialized from an owned
+// byte buffer, which the buffer types take ownership of without copying.
+
+// Borrowed byte slices (such as Cow<[u8]>) need no helper beyond serde_bytes
+// (or the vec_u8_as_binary option of SerializerConfig).
+
+// #[serde(with = "serde_rusoto_dynamodb::byte_buffers::bytes")]
+
+use serde::de::{Error as SerdeDeError, SeqAccess, Visitor};
+use std::fmt::{Formatter, Result as StdFmtResult};
+
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut Formatter) -> StdFmtResult {
+        f.write_str("a byte array")
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: SerdeDeError,
+    {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: SerdeDeError,
+    {
+        Ok(value)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+
+        Ok(bytes)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Bytes
+
+pub mod bytes {
+    use super::ByteBufVisitor;
+    use bytes1::Bytes;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Bytes, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(value)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Bytes, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_byte_buf(ByteBufVisitor)
+            .map(Bytes::from)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// BytesMut
+
+pub mod bytes_mut {
+    use super::ByteBufVisitor;
+    use bytes1::{Bytes, BytesMut};
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &BytesMut, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(value)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BytesMut, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_byte_buf(ByteBufVisitor)
+            .map(|bytes| match Bytes::from(bytes).try_into_mut() {
+                Ok(bytes) => bytes,
+                Err(bytes) => BytesMut::from(&bytes[..]),
+            })
+    }
+}
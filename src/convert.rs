@@ -0,0 +1,181 @@
+// Conversion
+
+// Traits for converting types with a canonical AttributeValue form directly,
+// without going through Serde, giving the same results as serializing and
+// deserializing with the default configuration. Serde compiles down to much
+// the same code for scalar values, so the direct conversions are not markedly
+// faster (see the convert benchmark), but they are useful as bounds for code
+// which converts known types, and avoid the Serde machinery entirely.
+
+// Blanket implementations for all Serialize and Deserialize types would
+// conflict with the direct implementations, so any other type can be
+// converted through Serde by wrapping it in the Serde type, which implements
+// both traits using the default configuration.
+
+use super::{
+    de::{self, AttributeValueNumberDeserializer, DeserializerConfig},
+    kind::AttributeKind,
+    result::{Error, Result},
+    ser::{self, format_float, format_int},
+};
+use rusoto_dynamodb::AttributeValue;
+use serde::{de::DeserializeOwned, Serialize};
+
+pub trait ToAttributeValue {
+    fn to_attribute_value(&self) -> Result<AttributeValue>;
+}
+
+pub trait FromAttributeValue: Sized {
+    fn from_attribute_value(value: &AttributeValue) -> Result<Self>;
+}
+
+fn number<'a>(
+    value: &'a AttributeValue,
+    config: &'a DeserializerConfig,
+) -> Result<AttributeValueNumberDeserializer<'a, 'a>> {
+    match value {
+        AttributeValue { n: Some(n), .. } => Ok(AttributeValueNumberDeserializer::new(n, config)),
+        value => Err(Error::new(&format!(
+            "Number Value Expected, Found {}",
+            AttributeKind::of(value)
+        ))),
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Boolean
+
+impl ToAttributeValue for bool {
+    fn to_attribute_value(&self) -> Result<AttributeValue> {
+        Ok(AttributeValue {
+            bool: Some(*self),
+            ..AttributeValue::default()
+        })
+    }
+}
+
+impl FromAttributeValue for bool {
+    fn from_attribute_value(value: &AttributeValue) -> Result<Self> {
+        match value {
+            AttributeValue { bool: Some(b), .. } => Ok(*b),
+            value => Err(Error::new(&format!(
+                "Boolean Value Expected, Found {}",
+                AttributeKind::of(value)
+            ))),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Numeric
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl ToAttributeValue for $t {
+                fn to_attribute_value(&self) -> Result<AttributeValue> {
+                    Ok(AttributeValue {
+                        n: Some(format_int(*self)),
+                        ..AttributeValue::default()
+                    })
+                }
+            }
+
+            impl FromAttributeValue for $t {
+                fn from_attribute_value(value: &AttributeValue) -> Result<Self> {
+                    number(value, &DeserializerConfig::default())?.integer()
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl ToAttributeValue for f32 {
+    fn to_attribute_value(&self) -> Result<AttributeValue> {
+        Ok(AttributeValue {
+            n: Some(format_float(*self, str::to_owned)),
+            ..AttributeValue::default()
+        })
+    }
+}
+
+impl FromAttributeValue for f32 {
+    fn from_attribute_value(value: &AttributeValue) -> Result<Self> {
+        number(value, &DeserializerConfig::default())?.f32()
+    }
+}
+
+impl ToAttributeValue for f64 {
+    fn to_attribute_value(&self) -> Result<AttributeValue> {
+        Ok(AttributeValue {
+            n: Some(format_float(*self, str::to_owned)),
+            ..AttributeValue::default()
+        })
+    }
+}
+
+impl FromAttributeValue for f64 {
+    fn from_attribute_value(value: &AttributeValue) -> Result<Self> {
+        number(value, &DeserializerConfig::default())?.f64()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// String
+
+impl ToAttributeValue for str {
+    fn to_attribute_value(&self) -> Result<AttributeValue> {
+        Ok(AttributeValue {
+            s: Some(self.to_owned()),
+            ..AttributeValue::default()
+        })
+    }
+}
+
+impl ToAttributeValue for String {
+    fn to_attribute_value(&self) -> Result<AttributeValue> {
+        self.as_str().to_attribute_value()
+    }
+}
+
+impl FromAttributeValue for String {
+    fn from_attribute_value(value: &AttributeValue) -> Result<Self> {
+        match value {
+            AttributeValue { s: Some(s), .. } => Ok(s.to_owned()),
+            value => Err(Error::new(&format!(
+                "String Value Expected, Found {}",
+                AttributeKind::of(value)
+            ))),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Serde
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Serde<T>(pub T);
+
+impl<T> Serde<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Serialize> ToAttributeValue for Serde<T> {
+    fn to_attribute_value(&self) -> Result<AttributeValue> {
+        ser::to_attribute_value(&self.0)
+    }
+}
+
+impl<T: DeserializeOwned> FromAttributeValue for Serde<T> {
+    fn from_attribute_value(value: &AttributeValue) -> Result<Self> {
+        de::from_attribute_value(value).map(Serde)
+    }
+}
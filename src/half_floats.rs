@@ -0,0 +1,67 @@
+// Half Floats
+
+// Helpers for use with serde(with) to store the half precision float types of
+// the half crate (f16 and bf16) as the native AWS AttributeValue number type,
+// available with the "half" feature. Values are serialized through their
+// (exact) f32 conversion, so the shortest f32 form is written, and are checked
+// against the range of the target type when deserialized.
+
+// #[serde(with = "serde_rusoto_dynamodb::half_floats::f16")]
+
+// -----------------------------------------------------------------------------
+
+// f16
+
+pub mod f16 {
+    use half::f16;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &f16, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f32(value.to_f32())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<f16, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match f32::deserialize(deserializer)? {
+            f if f.is_finite() && f.abs() > f16::MAX.to_f32() => Err(D::Error::custom(format!(
+                "Number {} Out Of Range For f16",
+                f
+            ))),
+            f => Ok(f16::from_f32(f)),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// bf16
+
+pub mod bf16 {
+    use half::bf16;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &bf16, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f32(value.to_f32())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<bf16, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match f32::deserialize(deserializer)? {
+            f if f.is_finite() && f.abs() > bf16::MAX.to_f32() => Err(D::Error::custom(format!(
+                "Number {} Out Of Range For bf16",
+                f
+            ))),
+            f => Ok(bf16::from_f32(f)),
+        }
+    }
+}
@@ -0,0 +1,496 @@
+// DynamoDB JSON
+
+// Import items from the typed DynamoDB JSON format (as used by the AWS CLI,
+// console, and table exports), where each value is an object with a single key
+// naming the type, such as { "id": { "S": "abc" }, "count": { "N": "42" } },
+// and binary values are base64 encoded. Available with the "json" feature.
+
+// Items may also be read from a reader holding many items, as a JSON array of
+// items, as newline-delimited items, or as the output of a CLI scan or query
+// (an object holding the array of items as "Items").
+
+// Items are parsed directly (rather than through an intermediate JSON value)
+// so that duplicate keys within a map can be detected, and rejected where
+// configured.
+
+use super::{
+    de::DeserializerConfig,
+    result::{Error, Result},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rusoto_dynamodb::AttributeValue;
+use serde::de::{
+    DeserializeSeed, Deserializer, Error as SerdeDeError, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use std::{
+    collections::HashMap,
+    fmt::{Formatter, Result as StdFmtResult},
+    io::{BufRead, BufReader, Read},
+    result::Result as StdResult,
+};
+
+// -----------------------------------------------------------------------------
+
+// Item Seed
+
+#[derive(Clone, Copy, new)]
+struct ItemSeed<'a> {
+    config: &'a DeserializerConfig,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for ItemSeed<'a> {
+    type Value = HashMap<String, AttributeValue>;
+
+    fn deserialize<D>(self, deserializer: D) -> StdResult<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for ItemSeed<'a> {
+    type Value = HashMap<String, AttributeValue>;
+
+    fn expecting(&self, f: &mut Formatter) -> StdFmtResult {
+        f.write_str("a DynamoDB JSON item")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> StdResult<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut item = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+
+        while let Some(name) = map.next_key::<String>()? {
+            if self.config.reject_duplicate_keys && item.contains_key(&name) {
+                return Err(A::Error::custom(format!("Duplicate Key {}", name)));
+            }
+
+            let value = map.next_value_seed(ValueSeed::new(self.config))?;
+
+            item.insert(name, value);
+        }
+
+        Ok(item)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// List Seed
+
+#[derive(Clone, Copy, new)]
+struct ListSeed<'a> {
+    config: &'a DeserializerConfig,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for ListSeed<'a> {
+    type Value = Vec<AttributeValue>;
+
+    fn deserialize<D>(self, deserializer: D) -> StdResult<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for ListSeed<'a> {
+    type Value = Vec<AttributeValue>;
+
+    fn expecting(&self, f: &mut Formatter) -> StdFmtResult {
+        f.write_str("a DynamoDB JSON list")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> StdResult<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(value) = seq.next_element_seed(ValueSeed::new(self.config))? {
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Value Seed
+
+// Parse typed values, requiring an object with a single type key, and
+// decoding base64 encoded binary values.
+
+#[derive(Clone, Copy, new)]
+struct ValueSeed<'a> {
+    config: &'a DeserializerConfig,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for ValueSeed<'a> {
+    type Value = AttributeValue;
+
+    fn deserialize<D>(self, deserializer: D) -> StdResult<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for ValueSeed<'a> {
+    type Value = AttributeValue;
+
+    fn expecting(&self, f: &mut Formatter) -> StdFmtResult {
+        f.write_str("a DynamoDB JSON typed value")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> StdResult<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let decode = |b: String| {
+            STANDARD
+                .decode(b)
+                .map(Into::into)
+                .map_err(|e| A::Error::custom(format!("Base64 Value Expected: {}", e)))
+        };
+
+        let value = match map.next_key::<String>()? {
+            Some(kind) => match kind.as_str() {
+                "B" => AttributeValue {
+                    b: Some(decode(map.next_value()?)?),
+                    ..AttributeValue::default()
+                },
+                "BOOL" => AttributeValue {
+                    bool: Some(map.next_value()?),
+                    ..AttributeValue::default()
+                },
+                "BS" => AttributeValue {
+                    bs: Some(
+                        map.next_value::<Vec<String>>()?
+                            .into_iter()
+                            .map(decode)
+                            .collect::<StdResult<_, _>>()?,
+                    ),
+                    ..AttributeValue::default()
+                },
+                "L" => AttributeValue {
+                    l: Some(map.next_value_seed(ListSeed::new(self.config))?),
+                    ..AttributeValue::default()
+                },
+                "M" => AttributeValue {
+                    m: Some(map.next_value_seed(ItemSeed::new(self.config))?),
+                    ..AttributeValue::default()
+                },
+                "N" => AttributeValue {
+                    n: Some(map.next_value()?),
+                    ..AttributeValue::default()
+                },
+                "NS" => AttributeValue {
+                    ns: Some(map.next_value()?),
+                    ..AttributeValue::default()
+                },
+                "NULL" => AttributeValue {
+                    null: Some(map.next_value()?),
+                    ..AttributeValue::default()
+                },
+                "S" => AttributeValue {
+                    s: Some(map.next_value()?),
+                    ..AttributeValue::default()
+                },
+                "SS" => AttributeValue {
+                    ss: Some(map.next_value()?),
+                    ..AttributeValue::default()
+                },
+                kind => return Err(A::Error::custom(format!("Unknown Type {}", kind))),
+            },
+            _ => return Err(A::Error::custom("Typed Value Expected")),
+        };
+
+        match map.next_key::<String>()? {
+            Some(_) => Err(A::Error::custom("Typed Value With A Single Type Expected")),
+            _ => Ok(value),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Items Seed
+
+// Parse a top level value of a reader, either an array of items, or an object
+// which is either an item or the output of a CLI scan or query. An object is
+// treated as output only where it has an array of items under the "Items" key
+// (which the CLI writes first), and the other values of the output (such as
+// the last evaluated key) are then ignored. Otherwise every value must be a
+// typed value, and an object with any other value (including the output of a
+// scan or query which selected only the count) is rejected rather than
+// silently producing no items.
+
+type Item = HashMap<String, AttributeValue>;
+
+#[derive(Clone, Copy, new)]
+struct ItemsSeed<'a> {
+    config: &'a DeserializerConfig,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for ItemsSeed<'a> {
+    type Value = Vec<Item>;
+
+    fn deserialize<D>(self, deserializer: D) -> StdResult<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for ItemsSeed<'a> {
+    type Value = Vec<Item>;
+
+    fn expecting(&self, f: &mut Formatter) -> StdFmtResult {
+        f.write_str("DynamoDB JSON items")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> StdResult<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(item) = seq.next_element_seed(ItemSeed::new(self.config))? {
+            items.push(item);
+        }
+
+        Ok(items)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> StdResult<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut item = HashMap::new();
+        let mut state = State::Item;
+
+        while let Some(name) = map.next_key::<String>()? {
+            match state {
+                State::Output(ref mut items) if name == "Items" => {
+                    *items = map.next_value_seed(self)?
+                }
+                State::Output(_) => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+                State::Other(_) if name == "Items" => {
+                    state = State::Output(map.next_value_seed(self)?)
+                }
+                State::Other(_) => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+                State::Item if item.is_empty() => {
+                    match map.next_value_seed(EntrySeed::new(self))? {
+                        Entry::Value(value) => {
+                            item.insert(name, *value);
+                        }
+                        Entry::Items(items) if name == "Items" => state = State::Output(items),
+                        _ => state = State::Other(name),
+                    }
+                }
+                State::Item => {
+                    if self.config.reject_duplicate_keys && item.contains_key(&name) {
+                        return Err(A::Error::custom(format!("Duplicate Key {}", name)));
+                    }
+
+                    let value = map.next_value_seed(ValueSeed::new(self.config))?;
+
+                    item.insert(name, value);
+                }
+            }
+        }
+
+        match state {
+            State::Item => Ok(vec![item]),
+            State::Other(name) => Err(A::Error::custom(format!(
+                "Typed Value Expected For {}",
+                name
+            ))),
+            State::Output(items) => Ok(items),
+        }
+    }
+}
+
+// An object is read as an item until the first value shows otherwise, either
+// as the output of a scan or query (an array of items), or as some other value
+// (where the output may still follow, as the items need not be first).
+
+enum State {
+    Item,
+    Other(String),
+    Output(Vec<Item>),
+}
+
+// Entry Seed
+
+// Parse the first value of a top level object, which may be a typed value (for
+// an item), an array of items, or another value (for the output of a scan or
+// query).
+
+enum Entry {
+    Value(Box<AttributeValue>),
+    Items(Vec<Item>),
+    Other,
+}
+
+#[derive(new)]
+struct EntrySeed<'a> {
+    items: ItemsSeed<'a>,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for EntrySeed<'a> {
+    type Value = Entry;
+
+    fn deserialize<D>(self, deserializer: D) -> StdResult<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for EntrySeed<'a> {
+    type Value = Entry;
+
+    fn expecting(&self, f: &mut Formatter) -> StdFmtResult {
+        f.write_str("a DynamoDB JSON typed value or items")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> StdResult<Self::Value, E> {
+        Ok(Entry::Other)
+    }
+
+    fn visit_i64<E>(self, _: i64) -> StdResult<Self::Value, E> {
+        Ok(Entry::Other)
+    }
+
+    fn visit_u64<E>(self, _: u64) -> StdResult<Self::Value, E> {
+        Ok(Entry::Other)
+    }
+
+    fn visit_f64<E>(self, _: f64) -> StdResult<Self::Value, E> {
+        Ok(Entry::Other)
+    }
+
+    fn visit_str<E>(self, _: &str) -> StdResult<Self::Value, E> {
+        Ok(Entry::Other)
+    }
+
+    fn visit_unit<E>(self) -> StdResult<Self::Value, E> {
+        Ok(Entry::Other)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> StdResult<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.items.visit_seq(seq).map(Entry::Items)
+    }
+
+    fn visit_map<A>(self, map: A) -> StdResult<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        ValueSeed::new(self.items.config)
+            .visit_map(map)
+            .map(|value| Entry::Value(Box::new(value)))
+    }
+}
+
+// =============================================================================
+
+// DynamoDB JSON Import Functions
+
+// Import with non-default configuration is available through the
+// from_dynamodb_json_with_config function.
+
+pub fn from_dynamodb_json(json: &str) -> Result<HashMap<String, AttributeValue>> {
+    from_dynamodb_json_with_config(json, &DeserializerConfig::default())
+}
+
+pub fn from_dynamodb_json_with_config(
+    json: &str,
+    config: &DeserializerConfig,
+) -> Result<HashMap<String, AttributeValue>> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let item = ItemSeed::new(config)
+        .deserialize(&mut deserializer)
+        .map_err(json_error)?;
+
+    deserializer.end().map_err(json_error)?;
+
+    Ok(item)
+}
+
+// Items are read one top level value at a time (as the values of a
+// newline-delimited file may each be an item), so that a large file is not read
+// in to memory at once. The line and column of an error are reported relative
+// to the start of the value containing the error.
+
+pub fn items_from_dynamodb_json_reader<R>(reader: R) -> Result<Vec<HashMap<String, AttributeValue>>>
+where
+    R: Read,
+{
+    items_from_dynamodb_json_reader_with_config(reader, &DeserializerConfig::default())
+}
+
+pub fn items_from_dynamodb_json_reader_with_config<R>(
+    reader: R,
+    config: &DeserializerConfig,
+) -> Result<Vec<HashMap<String, AttributeValue>>>
+where
+    R: Read,
+{
+    let mut reader = BufReader::new(reader);
+    let mut items = Vec::new();
+
+    while skip_whitespace(&mut reader)? {
+        let mut deserializer = serde_json::Deserializer::from_reader(&mut reader);
+
+        items.extend(
+            ItemsSeed::new(config)
+                .deserialize(&mut deserializer)
+                .map_err(json_error)?,
+        );
+    }
+
+    Ok(items)
+}
+
+// Skip whitespace between top level values, returning whether another value
+// follows.
+
+fn skip_whitespace<R>(reader: &mut R) -> Result<bool>
+where
+    R: BufRead,
+{
+    loop {
+        let buffer = reader.fill_buf().map_err(|e| Error::new(&e.to_string()))?;
+
+        match buffer.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(position) => {
+                reader.consume(position);
+                return Ok(true);
+            }
+            None if buffer.is_empty() => return Ok(false),
+            None => {
+                let len = buffer.len();
+                reader.consume(len);
+            }
+        }
+    }
+}
+
+fn json_error(e: serde_json::Error) -> Error {
+    Error::new(&e.to_string())
+}
@@ -0,0 +1,71 @@
+// Attribute Kinds
+
+// -----------------------------------------------------------------------------
+
+// Attribute Kind
+
+// A simple classification of the AWS AttributeValue type by the (first) type
+// field which is set, useful for reporting what kind of value was found (or
+// produced) when a value of some other kind was expected.
+
+use rusoto_dynamodb::AttributeValue;
+use std::fmt::{Display, Formatter, Result as StdFmtResult};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AttributeKind {
+    Binary,
+    BinarySet,
+    Bool,
+    Empty,
+    List,
+    Map,
+    Null,
+    Number,
+    NumberSet,
+    String,
+    StringSet,
+}
+
+impl AttributeKind {
+    pub fn of(value: &AttributeValue) -> Self {
+        match value {
+            AttributeValue { b: Some(_), .. } => AttributeKind::Binary,
+            AttributeValue { bs: Some(_), .. } => AttributeKind::BinarySet,
+            AttributeValue { bool: Some(_), .. } => AttributeKind::Bool,
+            AttributeValue { l: Some(_), .. } => AttributeKind::List,
+            AttributeValue { m: Some(_), .. } => AttributeKind::Map,
+            AttributeValue { n: Some(_), .. } => AttributeKind::Number,
+            AttributeValue { ns: Some(_), .. } => AttributeKind::NumberSet,
+            AttributeValue { null: Some(_), .. } => AttributeKind::Null,
+            AttributeValue { s: Some(_), .. } => AttributeKind::String,
+            AttributeValue { ss: Some(_), .. } => AttributeKind::StringSet,
+            _ => AttributeKind::Empty,
+        }
+    }
+}
+
+impl From<&AttributeValue> for AttributeKind {
+    fn from(value: &AttributeValue) -> Self {
+        AttributeKind::of(value)
+    }
+}
+
+impl Display for AttributeKind {
+    fn fmt(&self, f: &mut Formatter) -> StdFmtResult {
+        let name = match self {
+            AttributeKind::Binary => "Binary",
+            AttributeKind::BinarySet => "Binary Set",
+            AttributeKind::Bool => "Boolean",
+            AttributeKind::Empty => "Empty",
+            AttributeKind::List => "List",
+            AttributeKind::Map => "Map",
+            AttributeKind::Null => "Null",
+            AttributeKind::Number => "Number",
+            AttributeKind::NumberSet => "Number Set",
+            AttributeKind::String => "String",
+            AttributeKind::StringSet => "String Set",
+        };
+
+        Display::fmt(name, f)
+    }
+}
@@ -1,6 +1,24 @@
+#[cfg(feature = "bytes")]
+pub mod byte_buffers;
+pub mod convert;
 pub mod de;
+#[cfg(feature = "half")]
+pub mod half_floats;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod kind;
+#[cfg(feature = "outputs")]
+pub mod outputs;
+pub mod padded_number;
+mod path;
+pub mod prelude;
 pub mod result;
 pub mod ser;
+#[cfg(feature = "streams")]
+pub mod streams;
+pub mod ttl;
+pub mod types;
+pub mod util;
 
 #[macro_use]
 extern crate derive_new;
@@ -9,9 +27,25 @@ extern crate derive_new;
 
 // Attribute Value Serialization/Deserialization Functions
 
-// The small public interface for ser/de, which may well be augmented at a later
-// stage with a higher level set of functions for assuming a top level "map"
-// type for easy integration with AWS (Rusoto) APIs.
+// The small public interface for ser/de, along with a higher level set of
+// functions assuming a top level "map" type (an item) for easy integration with
+// AWS (Rusoto) APIs. The prelude module re-exports these for glob import.
 
-pub use de::from_attribute_value;
-pub use ser::to_attribute_value;
+// Configurable variants of each function are also exposed, taking a
+// SerializerConfig or DeserializerConfig respectively.
+
+pub use convert::{FromAttributeValue, Serde, ToAttributeValue};
+pub use de::{
+    from_attribute_value, from_attribute_value_owned, from_attribute_value_owned_with_config,
+    from_attribute_value_with_config, from_item, from_item_with_config, from_items,
+    from_items_with_config, BinaryRepresentation, DeserializerConfig,
+};
+pub use ser::{
+    to_attribute_value, to_attribute_value_ref, to_attribute_value_with_config,
+    to_expression_values, to_expression_values_with_config, to_item, to_item_with_config,
+    to_items_iter, to_items_iter_with_config, AttributeHook, Coercion, SerializerConfig,
+};
+pub use types::{
+    as_binary_set, as_number_set, as_string_set, BinarySet, ByteArray, DoubleOption, NumberSet,
+    OwnedBytes, StringSet,
+};
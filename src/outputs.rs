@@ -0,0 +1,67 @@
+// Outputs
+
+// Deserialize items directly from the output types of the DynamoDB API calls
+// which return them, removing the common glue code of extracting the (optional)
+// items before deserializing. Available with the "outputs" feature.
+
+use super::{
+    de::{self, DeserializerConfig},
+    result::Result,
+};
+use rusoto_dynamodb::{GetItemOutput, QueryOutput};
+use serde::Deserialize;
+
+// -----------------------------------------------------------------------------
+
+// Get Item
+
+// The item of a GetItemOutput is absent where no item matched the key given,
+// which is not an error, and so gives None.
+
+pub fn from_get_item_output<'a, T>(output: &'a GetItemOutput) -> Result<Option<T>>
+where
+    T: Deserialize<'a>,
+{
+    from_get_item_output_with_config(output, &DeserializerConfig::default())
+}
+
+pub fn from_get_item_output_with_config<'a, T>(
+    output: &'a GetItemOutput,
+    config: &DeserializerConfig,
+) -> Result<Option<T>>
+where
+    T: Deserialize<'a>,
+{
+    output
+        .item
+        .as_ref()
+        .map(|item| de::from_item_with_config(item, config))
+        .transpose()
+}
+
+// -----------------------------------------------------------------------------
+
+// Query
+
+// The items of a QueryOutput are absent where no items matched the query, and
+// so give an empty Vec.
+
+pub fn from_query_output<'a, T>(output: &'a QueryOutput) -> Result<Vec<T>>
+where
+    T: Deserialize<'a>,
+{
+    from_query_output_with_config(output, &DeserializerConfig::default())
+}
+
+pub fn from_query_output_with_config<'a, T>(
+    output: &'a QueryOutput,
+    config: &DeserializerConfig,
+) -> Result<Vec<T>>
+where
+    T: Deserialize<'a>,
+{
+    match &output.items {
+        Some(items) => de::from_items_with_config(items, config),
+        _ => Ok(Vec::new()),
+    }
+}
@@ -0,0 +1,57 @@
+// Padded Number
+
+// Helpers for use with serde(serialize_with/deserialize_with) to store unsigned
+// integers as fixed width, zero padded strings (rather than as the native AWS
+// AttributeValue number type), so that they sort numerically when compared as
+// strings, as in sort keys. The width is given as a const generic parameter,
+// and values which do not fit the width are rejected rather than written in a
+// form which would not sort correctly.
+
+// #[serde(
+//     serialize_with = "serde_rusoto_dynamodb::padded_number::serialize::<10, _, _>",
+//     deserialize_with = "serde_rusoto_dynamodb::padded_number::deserialize::<10, _, _>"
+// )]
+
+use serde::{
+    de::Error as SerdeDeError, ser::Error as SerdeSerError, Deserialize, Deserializer, Serializer,
+};
+use std::{fmt::Display, str::FromStr};
+
+pub fn serialize<const N: usize, T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    let digits = value.to_string();
+
+    match digits.bytes().all(|b| b.is_ascii_digit()) {
+        true if digits.len() <= N => serializer.serialize_str(&format!("{:0>N$}", digits)),
+        true => Err(S::Error::custom(format!(
+            "Number {} Exceeds Width Of {} Digits",
+            digits, N
+        ))),
+        _ => Err(S::Error::custom(format!(
+            "Unsigned Integer Expected, Found {}",
+            digits
+        ))),
+    }
+}
+
+pub fn deserialize<'de, const N: usize, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    let digits = String::deserialize(deserializer)?;
+
+    match digits.bytes().all(|b| b.is_ascii_digit()) {
+        true if !digits.is_empty() => digits
+            .parse()
+            .map_err(|e| D::Error::custom(format!("Number {} Is Invalid: {}", digits, e))),
+        _ => Err(D::Error::custom(format!(
+            "Padded Number Expected, Found {}",
+            digits
+        ))),
+    }
+}
@@ -0,0 +1,48 @@
+// Paths
+
+// -----------------------------------------------------------------------------
+
+// Path
+
+// A lightweight representation of the path to the value currently being
+// serialized, built from borrowed segments as the serializer descends in to
+// compound values, and only rendered (in a form such as "a.b[2].c") when
+// reporting an error. Used both when serializing and when validating values
+// (see util).
+
+use super::result::Error;
+use std::fmt::{Display, Formatter, Result as StdFmtResult};
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Path<'a> {
+    Root,
+    Field(&'a Path<'a>, &'a str),
+    Index(&'a Path<'a>, usize),
+}
+
+impl<'a> Path<'a> {
+    pub(crate) fn error(&self, message: &str) -> Error {
+        match self {
+            Path::Root => Error::new(message),
+            _ => Error::new(&format!("at {}: {}", self, message)),
+        }
+    }
+
+    pub(crate) fn depth(&self) -> usize {
+        match self {
+            Path::Root => 0,
+            Path::Field(parent, _) | Path::Index(parent, _) => parent.depth() + 1,
+        }
+    }
+}
+
+impl<'a> Display for Path<'a> {
+    fn fmt(&self, f: &mut Formatter) -> StdFmtResult {
+        match self {
+            Path::Root => Ok(()),
+            Path::Field(Path::Root, name) => write!(f, "{}", name),
+            Path::Field(parent, name) => write!(f, "{}.{}", parent, name),
+            Path::Index(parent, index) => write!(f, "{}[{}]", parent, index),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// Prelude
+
+// The common entry points, configuration and wrapper types, for convenient
+// import with use serde_rusoto_dynamodb::prelude::*;
+
+pub use super::{
+    convert::{FromAttributeValue, Serde, ToAttributeValue},
+    de::{
+        from_attribute_value, from_attribute_value_owned, from_attribute_value_owned_with_config,
+        from_attribute_value_with_config, from_item, from_item_with_config, from_items,
+        from_items_with_config, BinaryRepresentation, DeserializerConfig,
+    },
+    result::Error,
+    ser::{
+        to_attribute_value, to_attribute_value_ref, to_attribute_value_with_config,
+        to_expression_values, to_expression_values_with_config, to_item, to_item_with_config,
+        to_items_iter, to_items_iter_with_config, AttributeHook, Coercion, SerializerConfig,
+    },
+    types::{BinarySet, ByteArray, DoubleOption, NumberSet, OwnedBytes, StringSet},
+};
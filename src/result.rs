@@ -6,25 +6,66 @@
 
 // Implementation of a very simple combined Error type for both Serialization
 // and Deserialization, making error handling simpler, and with a convenient
-// constructor function.
+// constructor function. The Error type is Send + Sync + 'static, so it converts
+// cleanly in to boxed errors (and the error types of crates such as anyhow).
+
+// With the "backtrace" feature, a backtrace is captured when each error is
+// created (subject to the usual RUST_BACKTRACE and RUST_LIB_BACKTRACE
+// environment variables, so capture is cheap when disabled), available through
+// the backtrace function. Errors are compared (and debug formatted) by message
+// alone, so that backtraces do not swamp the output of failed assertions.
+
+// The backtrace field exists only with the feature, so that without it the
+// Error type keeps its simple public shape (and may be constructed or matched
+// as Error { message }). Code which must build with the feature enabled
+// should construct errors using Error::new, and match them with .. to ignore
+// the backtrace.
 
 use serde::{de::Error as SerdeDeError, ser::Error as SerdeSerError};
+#[cfg(feature = "backtrace")]
+use std::backtrace::Backtrace;
 use std::{
     error::Error as StdError,
-    fmt::{Display, Formatter, Result as StdFmtResult},
+    fmt::{Debug, Display, Formatter, Result as StdFmtResult},
 };
 
-#[derive(Debug, PartialEq)]
 pub struct Error {
     pub message: String,
+    #[cfg(feature = "backtrace")]
+    backtrace: Backtrace,
 }
 
 impl Error {
     pub fn new(message: &str) -> Self {
+        Self::from_message(message.to_owned())
+    }
+
+    fn from_message(message: String) -> Self {
         Self {
-            message: message.to_owned(),
+            message,
+            #[cfg(feature = "backtrace")]
+            backtrace: Backtrace::capture(),
         }
     }
+
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut Formatter) -> StdFmtResult {
+        f.debug_struct("Error")
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.message == other.message
+    }
 }
 
 impl Display for Error {
@@ -35,29 +76,17 @@ impl Display for Error {
 
 impl SerdeDeError for Error {
     fn custom<T: Display>(msg: T) -> Error {
-        Error {
-            message: format!("{}", msg),
-        }
+        Error::from_message(msg.to_string())
     }
 }
 
 impl SerdeSerError for Error {
     fn custom<T: Display>(msg: T) -> Error {
-        Error {
-            message: format!("{}", msg),
-        }
+        Error::from_message(msg.to_string())
     }
 }
 
-impl StdError for Error {
-    fn description(&self) -> &str {
-        &self.message
-    }
-
-    fn cause(&self) -> Option<&StdError> {
-        None
-    }
-}
+impl StdError for Error {}
 
 // -----------------------------------------------------------------------------
 
@@ -4,11 +4,150 @@
 // by DynamoDB (and related products such as Streams) and which is implemented
 // in Rust by the Rusoto family of libraries.
 
-use super::result::{Error, Result};
+use super::{
+    path::Path,
+    result::{Error, Result},
+    types::{set_kind, take_owned_bytes, OWNED_BYTES},
+};
 use bytes::Bytes;
 use maplit::hashmap;
 use rusoto_dynamodb::AttributeValue;
 use serde::ser::{Serialize, Serializer};
+#[cfg(not(feature = "fast-number-format"))]
+use std::fmt::Debug;
+
+// Serializer Configuration
+
+// Options which alter the conventions used when serializing, passed to the
+// *_with_config serialization functions. The default configuration matches the
+// behaviour of the simple serialization functions.
+
+// coercions: Functions applied to the serialized form of newtype structs, keyed
+// by the name of the newtype (as given to Serde), allowing specific types to be
+// stored differently (for example, an identifier newtype stored as binary)
+// without annotating each use. Defaults to empty.
+
+pub type Coercion = fn(AttributeValue) -> Result<AttributeValue>;
+
+// enum_tag and item_enum_tag: Optional attribute names under which the variant
+// name of an enum is stored, alongside the fields of the variant (inline),
+// rather than using the singly-keyed map form. The two differ only in which
+// enums they apply to:
+
+// - item_enum_tag applies only to an enum serialized as an entire item (at the
+//   top level), similar to the internally tagged representation in Serde, for
+//   polymorphic records stored in a single table (and such items can be
+//   deserialized using #[serde(tag = "...")] with the same name, or read back
+//   where the same item enum tag is configured for deserialization).
+// - enum_tag applies to every enum, matching the convention commonly used for
+//   polymorphic attributes by other AWS SDKs (such as a "type" attribute naming
+//   the subtype), and is read back where the same tag is configured for
+//   deserialization (see DeserializerConfig), without annotating the enum.
+
+// Where both are set, item_enum_tag is used for the top level enum, and
+// enum_tag for every other enum (including an enum within a newtype variant of
+// the top level enum). For either, unit variants store only the tag, newtype
+// variants containing a value which does not serialize to a map (such as the
+// variants of a Result<String, _>) fall back to the singly-keyed map form,
+// tuple variants are not supported, and unit variants used as map keys are
+// written as the variant name (never tagged). Both default to None (not
+// tagged).
+
+// human_readable: Whether the serializer reports itself as human readable,
+// which some types (such as IpAddr or SocketAddr) use to choose between a
+// readable string form and a more compact form. Where not human readable, the
+// octets of IpAddr and SocketAddr values are serialized as binary, while other
+// tuples and arrays of u8 are unaffected (including standalone Ipv4Addr and
+// Ipv6Addr values, which Serde serializes exactly as arrays, and which are
+// serialized as lists). Defaults to true (as with Serde itself).
+
+// max_value_bytes: An optional limit on the size (in bytes) of individual string
+// and binary values, catching oversized values (which DynamoDB would reject)
+// before they are sent. Defaults to None (no limit).
+
+// omit_none: Whether struct fields and map values which serialize to a bare
+// null (such as an Option which is None) are omitted entirely rather than
+// stored as the native null representation. Defaults to false.
+
+// on_attribute: An optional function called with the name and serialized value
+// of each field of a struct serialized as an entire item (at the top level),
+// allowing validation or auditing of attributes (such as enforcing naming
+// rules) without walking the result again. Fields which are omitted are not
+// observed. Defaults to None.
+
+pub type AttributeHook = fn(&str, &AttributeValue);
+
+// reject_duplicate_keys: Whether a map which serializes the same key more than
+// once (as a custom Serialize implementation may) is rejected, rather than the
+// later value replacing the earlier one. Keys are compared after serializing,
+// so distinct keys which serialize to the same string are also rejected, and
+// keys are rejected even where the earlier value was omitted (see omit_none).
+// Defaults to false.
+
+// trim_float_integers: Whether floats with no fractional part are written
+// without the trailing ".0" (so 2.0 is written as "2" rather than "2.0"), for
+// consumers which expect integral values in integer form. Defaults to false.
+
+// validate_names: Whether attribute names (struct fields and map keys) longer
+// than 255 bytes, and lists and maps nested more than 32 levels deep, are
+// rejected when serializing, catching values which DynamoDB would reject (at
+// least in some contexts, such as key attributes) before they are sent.
+// Defaults to false.
+
+// vec_u8_as_binary: Whether sequences of u8 (such as Vec<u8> or &[u8]) are
+// serialized as the native binary type, rather than as a list of numbers,
+// without annotating each field (for example with serde_bytes). Sequences are
+// detected by how their elements serialize, so other sequences of small numbers
+// are unaffected, but empty sequences (which have no elements to detect) are
+// still serialized as empty lists, and arrays (which Serde serializes as
+// tuples) are not affected (see the ByteArray type).
+// Defaults to false.
+
+#[derive(Clone, Debug)]
+pub struct SerializerConfig {
+    pub coercions: HashMap<&'static str, Coercion>,
+    pub enum_tag: Option<String>,
+    pub human_readable: bool,
+    pub item_enum_tag: Option<String>,
+    pub max_value_bytes: Option<usize>,
+    pub omit_none: bool,
+    pub on_attribute: Option<AttributeHook>,
+    pub reject_duplicate_keys: bool,
+    pub trim_float_integers: bool,
+    pub validate_names: bool,
+    pub vec_u8_as_binary: bool,
+}
+
+impl SerializerConfig {
+    fn omits(&self, value: &AttributeValue) -> bool {
+        self.omit_none
+            && *value
+                == AttributeValue {
+                    null: Some(true),
+                    ..AttributeValue::default()
+                }
+    }
+}
+
+impl Default for SerializerConfig {
+    fn default() -> Self {
+        Self {
+            coercions: HashMap::new(),
+            enum_tag: None,
+            human_readable: true,
+            item_enum_tag: None,
+            max_value_bytes: None,
+            omit_none: false,
+            on_attribute: None,
+            reject_duplicate_keys: false,
+            trim_float_integers: false,
+            validate_names: false,
+            vec_u8_as_binary: false,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
 
 // Attribute Value Serializer
 
@@ -18,12 +157,210 @@ use serde::ser::{Serialize, Serializer};
 // where relevant).
 
 #[derive(new)]
-struct AttributeValueSerializer;
+struct AttributeValueSerializer<'a> {
+    config: &'a SerializerConfig,
+    path: Path<'a>,
+    #[new(default)]
+    inline: bool,
+    #[new(default)]
+    octets: bool,
+}
 
+#[cfg(feature = "fast-number-format")]
 use itoa::Integer;
+#[cfg(feature = "fast-number-format")]
 use ryu::{Buffer, Float};
+use std::fmt::Display;
+
+const MAX_DEPTH: usize = 32;
+const MAX_NAME_BYTES: usize = 255;
+
+// Check the length of an attribute name (where names are validated), reporting
+// the path of the map containing the name.
+
+fn check_name(config: &SerializerConfig, path: &Path, name: &str) -> Result<()> {
+    match name.len() {
+        len if config.validate_names && len > MAX_NAME_BYTES => Err(path.error(&format!(
+            "Attribute Name Of {} Bytes Exceeds Maximum Of {} Bytes",
+            len, MAX_NAME_BYTES
+        ))),
+        _ => Ok(()),
+    }
+}
+
+// Check the size of string and binary values against the configured limit (if
+// any), reporting the path of any oversized value.
+
+fn check_size(config: &SerializerConfig, path: &Path, len: usize) -> Result<()> {
+    match config.max_value_bytes {
+        Some(max) if len > max => Err(path.error(&format!(
+            "Value Of {} Bytes Exceeds Maximum Of {} Bytes",
+            len, max
+        ))),
+        _ => Ok(()),
+    }
+}
+
+use std::hash::Hash;
+
+// Remove the repeated elements of a set, keeping the first occurrence of each
+// (see serialize_set).
+
+fn distinct<T>(elements: Vec<T>) -> Vec<T>
+where
+    T: Eq + Hash,
+{
+    let mut seen = HashSet::with_capacity(elements.len());
+    let first = elements
+        .iter()
+        .map(|element| seen.insert(element))
+        .collect::<Vec<_>>();
+
+    elements
+        .into_iter()
+        .zip(first)
+        .filter_map(|(element, first)| first.then_some(element))
+        .collect()
+}
+
+impl<'a> AttributeValueSerializer<'a> {
+    // Enum Tag
+
+    // The configured item enum tag applies only to enums serialized as an
+    // entire item, and so only at the root of the value being serialized,
+    // while the configured enum tag (if any) applies elsewhere. The value of a
+    // tagged newtype variant is serialized inline (at the same path as the
+    // variant), but is not itself the item enum, and so is never tagged with
+    // the item enum tag.
+
+    fn inline(self) -> Self {
+        Self {
+            inline: true,
+            ..self
+        }
+    }
+
+    fn enum_tag(&self) -> Option<&'a str> {
+        match self.path {
+            Path::Root if !self.inline => self
+                .config
+                .item_enum_tag
+                .as_deref()
+                .or(self.config.enum_tag.as_deref()),
+            _ => self.config.enum_tag.as_deref(),
+        }
+    }
+
+    // Octets
+
+    // The compact forms of network addresses (IpAddr and SocketAddr, which
+    // Serde serializes as newtype variants of enums with those names where not
+    // human readable) hold their octets as tuples of u8, which are serialized
+    // as binary. Serde serializes arrays of u8 exactly as it does octets, so
+    // tuples are only collected as binary within the variants of those enums.
+
+    fn octets(self, octets: bool) -> Self {
+        Self { octets, ..self }
+    }
+
+    // Depth
+
+    // Check the nesting depth of a list or map (where names are validated),
+    // given the number of levels the value adds beyond the current path (as
+    // variants add a level for the map containing the variant). The top level
+    // value (usually the item) is not counted as a level of nesting.
+
+    fn check_depth(&self, levels: usize) -> Result<()> {
+        match self.path.depth() + levels {
+            depth if self.config.validate_names && depth > MAX_DEPTH => Err(self
+                .path
+                .error(&format!("Nesting Exceeds Maximum Depth Of {}", MAX_DEPTH))),
+            _ => Ok(()),
+        }
+    }
+
+    // Size
+
+    // Check the size of string and binary values (see check_size).
+
+    fn check_size(&self, len: usize) -> Result<()> {
+        check_size(self.config, &self.path, len)
+    }
+
+    // Set
+
+    // Convert the list produced by serializing the collection within a set
+    // wrapper type to the relevant native set type, requiring that the set is
+    // not empty and that each element is of the matching scalar kind. Repeated
+    // elements (as a Vec may hold) are removed, keeping the first of each, as
+    // DynamoDB rejects sets containing duplicates.
+
+    fn serialize_set(&self, kind: AttributeKind, value: AttributeValue) -> Result<AttributeValue> {
+        let values = match value {
+            AttributeValue { l: Some(l), .. } if l.is_empty() => {
+                return Err(self.path.error(&format!("{} Must Not Be Empty", kind)))
+            }
+            AttributeValue { l: Some(l), .. } => l,
+            value => {
+                return Err(self.path.error(&format!(
+                    "{} Must Serialize From Seq, Got {}",
+                    kind,
+                    AttributeKind::of(&value)
+                )))
+            }
+        };
+
+        let element = |value: &AttributeValue| {
+            self.path.error(&format!(
+                "{} Elements Must Be Of A Matching Kind, Got {}",
+                kind,
+                AttributeKind::of(value)
+            ))
+        };
+
+        match kind {
+            AttributeKind::BinarySet => Ok(AttributeValue {
+                bs: Some(
+                    values
+                        .into_iter()
+                        .map(|value| match value {
+                            AttributeValue { b: Some(b), .. } => Ok(b),
+                            value => Err(element(&value)),
+                        })
+                        .collect::<Result<_>>()
+                        .map(distinct)?,
+                ),
+                ..AttributeValue::default()
+            }),
+            AttributeKind::NumberSet => Ok(AttributeValue {
+                ns: Some(
+                    values
+                        .into_iter()
+                        .map(|value| match value {
+                            AttributeValue { n: Some(n), .. } => Ok(n),
+                            value => Err(element(&value)),
+                        })
+                        .collect::<Result<_>>()
+                        .map(distinct)?,
+                ),
+                ..AttributeValue::default()
+            }),
+            _ => Ok(AttributeValue {
+                ss: Some(
+                    values
+                        .into_iter()
+                        .map(|value| match value {
+                            AttributeValue { s: Some(s), .. } => Ok(s),
+                            value => Err(element(&value)),
+                        })
+                        .collect::<Result<_>>()
+                        .map(distinct)?,
+                ),
+                ..AttributeValue::default()
+            }),
+        }
+    }
 
-impl AttributeValueSerializer {
     // Numeric
 
     // Implementations of numeric value serializtion helper functions - made
@@ -34,27 +371,89 @@ impl AttributeValueSerializer {
     // underlying implementation of a string formatted number, as the AWS
     // AttributeValue representation of a number is a string value.
 
+    // When the (default) "fast-number-format" feature is disabled, core
+    // formatting is used instead. Integer output is identical, while float
+    // output uses the shortest round-trip Debug form, which agrees with ryu for
+    // common values but may differ slightly for some (notably in exponent
+    // formatting).
+
+    // Floats with no fractional part are formatted with a trailing ".0", which
+    // is trimmed where configured (the trimmed form still parses as a float).
+    // Negative zero is formatted as "-0.0" (or "-0" where trimmed), and so
+    // keeps its sign when deserialized as a float, though DynamoDB itself may
+    // not preserve the sign of a stored zero.
+
+    #[cfg(feature = "fast-number-format")]
     fn serialize_float<F: Float>(&self, v: F) -> Result<AttributeValue> {
-        let mut buf = Buffer::new();
+        format_float(v, |s| self.serialize_float_str(s))
+    }
+
+    #[cfg(not(feature = "fast-number-format"))]
+    fn serialize_float<F: Debug>(&self, v: F) -> Result<AttributeValue> {
+        format_float(v, |s| self.serialize_float_str(s))
+    }
+
+    fn serialize_float_str(&self, s: &str) -> Result<AttributeValue> {
+        let s = match s {
+            s if self.config.trim_float_integers => s.strip_suffix(".0").unwrap_or(s),
+            s => s,
+        };
 
         Ok(AttributeValue {
-            n: Some(buf.format(v).to_owned()),
+            n: Some(s.to_owned()),
             ..AttributeValue::default()
         })
     }
 
+    #[cfg(feature = "fast-number-format")]
     fn serialize_int<I: Integer>(&self, v: I) -> Result<AttributeValue> {
-        let mut s = String::new();
-        itoa::fmt(&mut s, v).unwrap();
+        Ok(AttributeValue {
+            n: Some(format_int(v)),
+            ..AttributeValue::default()
+        })
+    }
 
+    #[cfg(not(feature = "fast-number-format"))]
+    fn serialize_int<I: Display>(&self, v: I) -> Result<AttributeValue> {
         Ok(AttributeValue {
-            n: Some(s.to_owned()),
+            n: Some(format_int(v)),
             ..AttributeValue::default()
         })
     }
 }
 
-impl Serializer for AttributeValueSerializer {
+// Number Formatting
+
+// The formatting of numbers is shared with the direct conversions (see the
+// convert module), so that both give identical results. Floats are formatted
+// to a buffer which is passed to the given function, avoiding allocating a
+// string where the formatted float is trimmed before use.
+
+#[cfg(feature = "fast-number-format")]
+pub(crate) fn format_float<F: Float, T>(v: F, f: impl FnOnce(&str) -> T) -> T {
+    let mut buf = Buffer::new();
+
+    f(buf.format(v))
+}
+
+#[cfg(not(feature = "fast-number-format"))]
+pub(crate) fn format_float<F: Debug, T>(v: F, f: impl FnOnce(&str) -> T) -> T {
+    f(&format!("{:?}", v))
+}
+
+#[cfg(feature = "fast-number-format")]
+pub(crate) fn format_int<I: Integer>(v: I) -> String {
+    let mut s = String::new();
+    itoa::fmt(&mut s, v).unwrap();
+    s
+}
+
+#[cfg(not(feature = "fast-number-format"))]
+pub(crate) fn format_int<I: Display>(v: I) -> String {
+    v.to_string()
+}
+
+impl<'a> Serializer for AttributeValueSerializer<'a> {
     // Return Types
 
     // Use the Rusoto AttributeValue as our primary "Ok" type and the custom
@@ -68,13 +467,13 @@ impl Serializer for AttributeValueSerializer {
     // Use a custom compound serializer for each of the type variables relevant
     // to Rust value serialization, implemented below.
 
-    type SerializeMap = AttributeValueMapSerializer;
-    type SerializeSeq = AttributeValueSeqTupleAndTupleStructSerializer;
-    type SerializeStruct = AttributeValueStructSerializer;
-    type SerializeStructVariant = AttributeValueStructVariantSerializer;
-    type SerializeTuple = AttributeValueSeqTupleAndTupleStructSerializer;
-    type SerializeTupleStruct = AttributeValueSeqTupleAndTupleStructSerializer;
-    type SerializeTupleVariant = AttributeValueTupleVariantSerializer;
+    type SerializeMap = AttributeValueMapSerializer<'a>;
+    type SerializeSeq = AttributeValueSeqTupleAndTupleStructSerializer<'a>;
+    type SerializeStruct = AttributeValueStructSerializer<'a>;
+    type SerializeStructVariant = AttributeValueStructVariantSerializer<'a>;
+    type SerializeTuple = AttributeValueSeqTupleAndTupleStructSerializer<'a>;
+    type SerializeTupleStruct = AttributeValueSeqTupleAndTupleStructSerializer<'a>;
+    type SerializeTupleVariant = AttributeValueTupleVariantSerializer<'a>;
 
     // Boolean
 
@@ -93,6 +492,8 @@ impl Serializer for AttributeValueSerializer {
     // Serialize numeric (float and int) values using the native number
     // representation of the AWS AttributeValue type (note that the data is
     // stored as a string, see the implementation of string serializers above).
+    // 128-bit integers are written in full, though DynamoDB supports at most 38
+    // significant digits, so the largest values will be rejected when stored.
 
     fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
         self.serialize_int(v)
@@ -126,6 +527,14 @@ impl Serializer for AttributeValueSerializer {
         self.serialize_int(v)
     }
 
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok> {
+        self.serialize_int(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok> {
+        self.serialize_int(v)
+    }
+
     fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
         self.serialize_float(v)
     }
@@ -140,6 +549,8 @@ impl Serializer for AttributeValueSerializer {
     // representation of the AWS AttributeValue type (a string of length 1).
 
     fn serialize_char(self, value: char) -> Result<Self::Ok> {
+        self.check_size(value.len_utf8())?;
+
         Ok(AttributeValue {
             s: Some(value.to_string()),
             ..AttributeValue::default()
@@ -152,18 +563,42 @@ impl Serializer for AttributeValueSerializer {
     // representation of the AWS AttributeValue type.
 
     fn serialize_str(self, value: &str) -> Result<Self::Ok> {
+        self.check_size(value.len())?;
+
         Ok(AttributeValue {
             s: Some(value.to_string()),
             ..AttributeValue::default()
         })
     }
 
+    // Serialize Display values (collected by types using collect_str) as a
+    // string, using the formatted String directly rather than copying it as
+    // the default implementation (via serialize_str) would.
+
+    fn collect_str<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Display,
+    {
+        let value = value.to_string();
+
+        self.check_size(value.len())?;
+
+        Ok(AttributeValue {
+            s: Some(value),
+            ..AttributeValue::default()
+        })
+    }
+
     // Bytes
 
     // Serialize byte slice values as a string using the native byte vector
-    // representation of the AWS AttributeValue type.
+    // representation of the AWS AttributeValue type. Serde provides a borrowed
+    // slice, so the bytes are copied (use the OwnedBytes wrapper type to avoid
+    // the copy for large values).
 
     fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok> {
+        self.check_size(value.len())?;
+
         Ok(AttributeValue {
             b: Some(Bytes::from(value)),
             ..AttributeValue::default()
@@ -176,24 +611,28 @@ impl Serializer for AttributeValueSerializer {
     // variable for SerializeMap (see the implementation later).
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
-        Ok(Default::default())
+        self.check_depth(0)?;
+
+        Ok(AttributeValueMapSerializer::new(self.config, self.path))
     }
 
     // Option
 
     // Serialize Option values by representing None as the native null
     // representation of the AWS AttributeValue type, and Some as the serialized
-    // form of the inner value.
+    // form of the inner value. Nested options therefore collapse, as Some(None)
+    // and None are both serialized as null (and so both deserialize as None),
+    // which the DoubleOption wrapper type avoids where the distinction matters.
 
     fn serialize_none(self) -> Result<Self::Ok> {
         self.serialize_unit()
     }
 
-    fn serialize_some<V: ?Sized>(self, value: &V) -> Result<Self::Ok>
+    fn serialize_some<V>(self, value: &V) -> Result<Self::Ok>
     where
-        V: Serialize,
+        V: ?Sized + Serialize,
     {
-        value.serialize(AttributeValueSerializer)
+        value.serialize(AttributeValueSerializer::new(self.config, self.path))
     }
 
     // Newtype
@@ -201,34 +640,80 @@ impl Serializer for AttributeValueSerializer {
     // Serialize the newtype forms in appropriate ways:
 
     // For newtype structs, represent as the serialized form of the newtype
-    // value.
+    // value, coerced by any coercion configured for the newtype name. The
+    // OwnedBytes and set wrapper types are the exception, handing off bytes
+    // directly, or converting a list to the relevant set type (see the types
+    // module).
 
     // For newtype variants, follow the library approach to variants of a single
     // key/value  in a map (using the native AWS AttributeValue map
     // representation) where the key represents the variant name and the value
     // the serialized form of the variant value (in this case, the serialized
-    // newtype form).
+    // newtype form). Where an enum tag applies, the value is serialized inline
+    // and tagged where it serializes to a map. Other values (such as the Ok of
+    // a Result<String, _>) can not hold the tag, and so are serialized again
+    // in the singly-keyed map form, which is read back whether or not a tag is
+    // configured.
 
-    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        value.serialize(AttributeValueSerializer)
+        if name == OWNED_BYTES {
+            if let Some(bytes) = take_owned_bytes() {
+                self.check_size(bytes.len())?;
+
+                return Ok(AttributeValue {
+                    b: Some(bytes),
+                    ..AttributeValue::default()
+                });
+            }
+        }
+
+        let value = value.serialize(AttributeValueSerializer::new(self.config, self.path))?;
+
+        if let Some(kind) = set_kind(name) {
+            return self.serialize_set(kind, value);
+        }
+
+        match self.config.coercions.get(name) {
+            Some(coerce) => coerce(value),
+            _ => Ok(value),
+        }
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(
+    fn serialize_newtype_variant<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
         variant: &'static str,
         value: &T,
     ) -> Result<Self::Ok>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
+        if let Some(tag) = self.enum_tag() {
+            let serializer = AttributeValueSerializer::new(self.config, self.path).inline();
+
+            if let AttributeValue { m: Some(mut m), .. } = value.serialize(serializer)? {
+                tag_item(&mut m, tag, variant)?;
+
+                return Ok(AttributeValue {
+                    m: Some(m),
+                    ..AttributeValue::default()
+                });
+            }
+        }
+
+        self.check_depth(0)?;
+
+        let path = Path::Field(&self.path, variant);
+        let serializer = AttributeValueSerializer::new(self.config, path)
+            .octets(matches!(name, "IpAddr" | "SocketAddr"));
+
         Ok(AttributeValue {
             m: Some(hashmap! {
-                variant.to_owned() => value.serialize(AttributeValueSerializer)?
+                variant.to_owned() => value.serialize(serializer)?
             }),
             ..AttributeValue::default()
         })
@@ -240,7 +725,12 @@ impl Serializer for AttributeValueSerializer {
     // variable for SerializeSeq (see the implementation later).
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
-        Ok(Default::default())
+        self.check_depth(0)?;
+
+        Ok(AttributeValueSeqTupleAndTupleStructSerializer::new(
+            self.config,
+            self.path,
+        ))
     }
 
     // Struct
@@ -254,7 +744,9 @@ impl Serializer for AttributeValueSerializer {
     // variable for SerializeStructVariant (see the implementation later).
 
     fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
-        Ok(Default::default())
+        self.check_depth(0)?;
+
+        Ok(AttributeValueStructSerializer::new(self.config, self.path))
     }
 
     fn serialize_struct_variant(
@@ -264,7 +756,14 @@ impl Serializer for AttributeValueSerializer {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant> {
+        let tag = self.enum_tag();
+
+        self.check_depth(if tag.is_some() { 0 } else { 1 })?;
+
         Ok(AttributeValueStructVariantSerializer::new(
+            self.config,
+            self.path,
+            tag,
             variant.to_owned(),
         ))
     }
@@ -283,7 +782,12 @@ impl Serializer for AttributeValueSerializer {
     // variable for SerializeTupleVariant(see the implementation later).
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
-        Ok(Default::default())
+        self.check_depth(0)?;
+
+        Ok(
+            AttributeValueSeqTupleAndTupleStructSerializer::new(self.config, self.path)
+                .octets(self.octets),
+        )
     }
 
     fn serialize_tuple_struct(
@@ -291,7 +795,12 @@ impl Serializer for AttributeValueSerializer {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct> {
-        Ok(Default::default())
+        self.check_depth(0)?;
+
+        Ok(AttributeValueSeqTupleAndTupleStructSerializer::new(
+            self.config,
+            self.path,
+        ))
     }
 
     fn serialize_tuple_variant(
@@ -301,7 +810,18 @@ impl Serializer for AttributeValueSerializer {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
+        if self.enum_tag().is_some() {
+            return Err(self.path.error(&format!(
+                "Tagged Variant {} Must Not Be A Tuple Variant",
+                variant
+            )));
+        }
+
+        self.check_depth(1)?;
+
         Ok(AttributeValueTupleVariantSerializer::new(
+            self.config,
+            self.path,
             variant.to_owned(),
         ))
     }
@@ -337,6 +857,19 @@ impl Serializer for AttributeValueSerializer {
         _variant_index: u32,
         variant: &'static str,
     ) -> Result<Self::Ok> {
+        if let Some(tag) = self.enum_tag() {
+            let mut m = HashMap::new();
+
+            tag_item(&mut m, tag, variant)?;
+
+            return Ok(AttributeValue {
+                m: Some(m),
+                ..AttributeValue::default()
+            });
+        }
+
+        self.check_depth(0)?;
+
         Ok(AttributeValue {
             m: Some(hashmap! {
                 variant.to_owned() => AttributeValue {
@@ -347,63 +880,117 @@ impl Serializer for AttributeValueSerializer {
             ..AttributeValue::default()
         })
     }
+
+    // Human Readable
+
+    // Report human readability as configured, allowing types with both a
+    // readable and a compact form to choose between them.
+
+    fn is_human_readable(&self) -> bool {
+        self.config.human_readable
+    }
 }
 
 // =============================================================================
 
 // Compound Serializers
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // -----------------------------------------------------------------------------
 
 // Attribute Value Map Serializer
 
 // Serialize Rust map values as the native AWS AttributeValue map type. Keys
-// must be strings, so we serialize the key values and reject non-string results
-// and use valid keys when inserting the next value in to the HashMap.
+// must be strings, so we serialize the key values with the map key serializer
+// (which rejects anything other than a string, reporting the kind of value the
+// key would produce) and use valid keys when inserting the next value in to the
+// HashMap.
 
+use super::kind::AttributeKind;
 use serde::ser::SerializeMap;
 
-#[derive(Default)]
-pub struct AttributeValueMapSerializer {
+#[derive(new)]
+pub struct AttributeValueMapSerializer<'a> {
+    config: &'a SerializerConfig,
+    path: Path<'a>,
+    #[new(default)]
     key: Option<String>,
+    #[new(default)]
     values: HashMap<String, AttributeValue>,
+    #[new(default)]
+    seen: HashSet<String>,
+}
+
+// Keys and values are serialized by shared functions, so that serializing an
+// entry (the common case) can serialize the key and value together, without
+// storing the key in between, while giving the same results as serializing
+// the key and value separately.
+
+impl<'a> AttributeValueMapSerializer<'a> {
+    fn key<T>(&self, key: &T) -> Result<String>
+    where
+        T: ?Sized + Serialize,
+    {
+        key.serialize(MapKeySerializer::new(self.config, &self.path))
+    }
+
+    fn insert<T>(&mut self, key: String, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        check_name(self.config, &self.path, &key)?;
+
+        if self.config.reject_duplicate_keys && !self.seen.insert(key.clone()) {
+            return Err(self
+                .path
+                .error(&format!("Duplicate Map Key {} Not Allowed", key)));
+        }
+
+        let value = value.serialize(AttributeValueSerializer::new(
+            self.config,
+            Path::Field(&self.path, &key),
+        ))?;
+
+        if !self.config.omits(&value) {
+            self.values.insert(key, value);
+        }
+        Ok(())
+    }
 }
 
-impl SerializeMap for AttributeValueMapSerializer {
+impl<'a> SerializeMap for AttributeValueMapSerializer<'a> {
     type Ok = AttributeValue;
     type Error = Error;
 
-    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<()>
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        match key.serialize(AttributeValueSerializer) {
-            Ok(AttributeValue { s: Some(s), .. }) => {
-                self.key = Some(s);
-                Ok(())
-            }
-            _ => Err(Error::new("Key Must Be String")),
-        }
+        self.key = Some(self.key(key)?);
+        Ok(())
     }
 
-    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        match (
-            self.key.to_owned(),
-            value.serialize(AttributeValueSerializer),
-        ) {
-            (Some(s), Ok(value)) => {
-                self.values.insert(s.to_owned(), value);
-                Ok(())
-            }
+        match self.key.take() {
+            Some(key) => self.insert(key, value),
             _ => Err(Error::new("Key Must Be Set and Value Must Be Serializable")),
         }
     }
 
+    fn serialize_entry<K, V>(&mut self, key: &K, value: &V) -> Result<()>
+    where
+        K: ?Sized + Serialize,
+        V: ?Sized + Serialize,
+    {
+        let key = self.key(key)?;
+
+        self.insert(key, value)
+    }
+
     fn end(self) -> Result<Self::Ok> {
         Ok(AttributeValue {
             m: Some(self.values),
@@ -414,6 +1001,386 @@ impl SerializeMap for AttributeValueMapSerializer {
 
 // -----------------------------------------------------------------------------
 
+// Map Key Serializer
+
+// Serialize map keys directly to strings, accepting strings (and characters),
+// and unit variants (such as unit enum keys) as the variant name, regardless
+// of any configured enum tag. Newtypes and options are serialized as their
+// inner value, and any other value is rejected.
+
+use serde::ser::Impossible;
+
+#[derive(new)]
+struct MapKeySerializer<'a, 'p> {
+    config: &'a SerializerConfig,
+    path: &'p Path<'p>,
+}
+
+impl<'a, 'p> MapKeySerializer<'a, 'p> {
+    fn reject(&self, kind: AttributeKind) -> Error {
+        self.path
+            .error(&format!("Map Key Must Serialize To String, Got {}", kind))
+    }
+}
+
+impl<'a, 'p> Serializer for MapKeySerializer<'a, 'p> {
+    type Ok = String;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn is_human_readable(&self) -> bool {
+        self.config.human_readable
+    }
+
+    // Strings
+
+    fn serialize_str(self, value: &str) -> Result<Self::Ok> {
+        Ok(value.to_owned())
+    }
+
+    fn serialize_char(self, value: char) -> Result<Self::Ok> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok> {
+        Ok(variant.to_owned())
+    }
+
+    // Wrappers
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    // Rejected
+
+    fn serialize_bool(self, _: bool) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Bool))
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_i128(self, _: i128) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_u128(self, _: u128) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Number))
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Binary))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Null))
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Null))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok> {
+        Err(self.reject(AttributeKind::Null))
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(self.reject(AttributeKind::Map))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(self.reject(AttributeKind::List))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(self.reject(AttributeKind::List))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(self.reject(AttributeKind::List))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(self.reject(AttributeKind::Map))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(self.reject(AttributeKind::Map))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(self.reject(AttributeKind::Map))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(self.reject(AttributeKind::Map))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Probes
+
+// Serializers which inspect a value without producing anything, used where the
+// serialization of a value depends on what kind of value it is. A probe
+// accepts only the kind of value it looks for, failing immediately for
+// anything else. Display values (collected by types using collect_str) are
+// rejected without being formatted, so probing a value never allocates.
+
+use serde::ser::Error as SerdeSerError;
+use std::{
+    error::Error as StdError,
+    fmt::{Formatter, Result as StdFmtResult},
+    result::Result as StdResult,
+};
+
+#[derive(Debug)]
+struct Mismatch;
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter) -> StdFmtResult {
+        f.write_str("Mismatch")
+    }
+}
+
+impl StdError for Mismatch {}
+
+impl SerdeSerError for Mismatch {
+    fn custom<T: Display>(_: T) -> Self {
+        Mismatch
+    }
+}
+
+// Generate the methods of a probe which reject a value, given the methods and
+// the types of their arguments (other than self).
+
+macro_rules! mismatch {
+    ($($method:ident($($arg:ty),*) -> $ok:ty;)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> StdResult<$ok, Mismatch> {
+                Err(Mismatch)
+            }
+        )*
+    };
+}
+
+macro_rules! mismatch_all {
+    ($ok:ty) => {
+        type Ok = $ok;
+        type Error = Mismatch;
+
+        type SerializeSeq = Impossible<$ok, Mismatch>;
+        type SerializeTuple = Impossible<$ok, Mismatch>;
+        type SerializeTupleStruct = Impossible<$ok, Mismatch>;
+        type SerializeTupleVariant = Impossible<$ok, Mismatch>;
+        type SerializeMap = Impossible<$ok, Mismatch>;
+        type SerializeStruct = Impossible<$ok, Mismatch>;
+        type SerializeStructVariant = Impossible<$ok, Mismatch>;
+
+        mismatch! {
+            serialize_bool(bool) -> $ok;
+            serialize_i8(i8) -> $ok;
+            serialize_i16(i16) -> $ok;
+            serialize_i32(i32) -> $ok;
+            serialize_i64(i64) -> $ok;
+            serialize_i128(i128) -> $ok;
+            serialize_u16(u16) -> $ok;
+            serialize_u32(u32) -> $ok;
+            serialize_u64(u64) -> $ok;
+            serialize_u128(u128) -> $ok;
+            serialize_f32(f32) -> $ok;
+            serialize_f64(f64) -> $ok;
+            serialize_char(char) -> $ok;
+            serialize_str(&str) -> $ok;
+            serialize_bytes(&[u8]) -> $ok;
+            serialize_none() -> $ok;
+            serialize_unit() -> $ok;
+            serialize_unit_variant(&'static str, u32, &'static str) -> $ok;
+            serialize_seq(Option<usize>) -> Self::SerializeSeq;
+            serialize_tuple(usize) -> Self::SerializeTuple;
+            serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+            serialize_tuple_variant(&'static str, u32, &'static str, usize)
+                -> Self::SerializeTupleVariant;
+            serialize_map(Option<usize>) -> Self::SerializeMap;
+            serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+            serialize_struct_variant(&'static str, u32, &'static str, usize)
+                -> Self::SerializeStructVariant;
+        }
+
+        fn serialize_some<T>(self, _: &T) -> StdResult<$ok, Mismatch>
+        where
+            T: ?Sized + Serialize,
+        {
+            Err(Mismatch)
+        }
+
+        fn serialize_newtype_struct<T>(self, _: &'static str, _: &T) -> StdResult<$ok, Mismatch>
+        where
+            T: ?Sized + Serialize,
+        {
+            Err(Mismatch)
+        }
+
+        fn serialize_newtype_variant<T>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> StdResult<$ok, Mismatch>
+        where
+            T: ?Sized + Serialize,
+        {
+            Err(Mismatch)
+        }
+
+        fn collect_str<T>(self, _: &T) -> StdResult<$ok, Mismatch>
+        where
+            T: ?Sized + Display,
+        {
+            Err(Mismatch)
+        }
+    };
+}
+
+// Marker Probe
+
+// Marker fields (PhantomData) carry no data, and so are omitted entirely rather
+// than stored as the null value they would otherwise serialize to. Markers are
+// recognised as the unit struct named PhantomData (as Serde serializes them).
+// Serde requires a default for missing fields, so marker fields should be
+// annotated with #[serde(default)] where they are also deserialized.
+
+struct MarkerProbe;
+
+impl Serializer for MarkerProbe {
+    mismatch_all!(());
+
+    mismatch! {
+        serialize_u8(u8) -> ();
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> StdResult<(), Mismatch> {
+        match name {
+            "PhantomData" => Ok(()),
+            _ => Err(Mismatch),
+        }
+    }
+}
+
+fn is_marker<V>(value: &V) -> bool
+where
+    V: ?Sized + Serialize,
+{
+    value.serialize(MarkerProbe).is_ok()
+}
+
+// Bytes Probe
+
+// Elements of sequences are collected as bytes (where sequences of u8 are
+// serialized as binary) where they serialize as a u8.
+
+struct BytesProbe;
+
+impl Serializer for BytesProbe {
+    mismatch_all!(u8);
+
+    mismatch! {
+        serialize_unit_struct(&'static str) -> u8;
+    }
+
+    fn serialize_u8(self, value: u8) -> StdResult<u8, Mismatch> {
+        Ok(value)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
 // Attribute Value Seq, Tuple and Tuple Struct Serializer
 
 // Serialize Rust seq values as the native AWS AttributeValue list type where
@@ -422,38 +1389,96 @@ impl SerializeMap for AttributeValueMapSerializer {
 // imply heteregenous lists).
 
 use serde::ser::{SerializeSeq, SerializeTuple, SerializeTupleStruct};
+use std::mem::take;
+
+// Sequences of u8 may be serialized as binary, as configured, where each
+// element of the sequence serializes as a u8 (however it is referred to, as
+// Serde serializes the elements of collections by reference), as may the
+// octets of network addresses (see octets, above), which are passed on to the
+// elements of the tuples of an address (as for the address and port of a
+// SocketAddr). Elements are collected as bytes (see the bytes probe) until an
+// element which is not a u8 is found, at which point the bytes collected so
+// far are serialized as numbers, as any other element.
 
-#[derive(Default)]
-pub struct AttributeValueSeqTupleAndTupleStructSerializer {
+#[derive(new)]
+pub struct AttributeValueSeqTupleAndTupleStructSerializer<'a> {
+    config: &'a SerializerConfig,
+    path: Path<'a>,
+    #[new(default)]
     values: Vec<AttributeValue>,
+    #[new(default)]
+    bytes: Vec<u8>,
+    #[new(default)]
+    octets: bool,
 }
 
-impl AttributeValueSeqTupleAndTupleStructSerializer {
-    fn serialize<T: ?Sized>(&mut self, elem: &T) -> Result<()>
+impl<'a> AttributeValueSeqTupleAndTupleStructSerializer<'a> {
+    fn octets(self, octets: bool) -> Self {
+        Self { octets, ..self }
+    }
+
+    fn serialize<T>(&mut self, elem: &T) -> Result<()>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.values.push(elem.serialize(AttributeValueSerializer)?);
+        let path = Path::Index(&self.path, self.values.len());
+        let serializer = AttributeValueSerializer::new(self.config, path).octets(self.octets);
+
+        self.values.push(elem.serialize(serializer)?);
         Ok(())
     }
 
+    fn serialize_byte<T>(&mut self, elem: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        if self.values.is_empty() {
+            match elem.serialize(BytesProbe) {
+                Ok(byte) => {
+                    self.bytes.push(byte);
+                    return Ok(());
+                }
+                _ => {
+                    for byte in take(&mut self.bytes) {
+                        self.serialize(&byte)?;
+                    }
+                }
+            }
+        }
+
+        self.serialize(elem)
+    }
+
     fn end(self) -> Result<AttributeValue> {
-        Ok(AttributeValue {
-            l: Some(self.values),
-            ..AttributeValue::default()
-        })
+        match self.bytes.is_empty() {
+            false => {
+                check_size(self.config, &self.path, self.bytes.len())?;
+
+                Ok(AttributeValue {
+                    b: Some(self.bytes.into()),
+                    ..AttributeValue::default()
+                })
+            }
+            _ => Ok(AttributeValue {
+                l: Some(self.values),
+                ..AttributeValue::default()
+            }),
+        }
     }
 }
 
-impl SerializeSeq for AttributeValueSeqTupleAndTupleStructSerializer {
+impl<'a> SerializeSeq for AttributeValueSeqTupleAndTupleStructSerializer<'a> {
     type Ok = AttributeValue;
     type Error = Error;
 
-    fn serialize_element<T: ?Sized>(&mut self, elem: &T) -> Result<()>
+    fn serialize_element<T>(&mut self, elem: &T) -> Result<()>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.serialize(elem)
+        match self.config.vec_u8_as_binary {
+            true => self.serialize_byte(elem),
+            _ => self.serialize(elem),
+        }
     }
 
     fn end(self) -> Result<Self::Ok> {
@@ -461,15 +1486,18 @@ impl SerializeSeq for AttributeValueSeqTupleAndTupleStructSerializer {
     }
 }
 
-impl SerializeTuple for AttributeValueSeqTupleAndTupleStructSerializer {
+impl<'a> SerializeTuple for AttributeValueSeqTupleAndTupleStructSerializer<'a> {
     type Ok = AttributeValue;
     type Error = Error;
 
-    fn serialize_element<T: ?Sized>(&mut self, elem: &T) -> Result<()>
+    fn serialize_element<T>(&mut self, elem: &T) -> Result<()>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.serialize(elem)
+        match self.octets {
+            true => self.serialize_byte(elem),
+            _ => self.serialize(elem),
+        }
     }
 
     fn end(self) -> Result<AttributeValue> {
@@ -477,13 +1505,13 @@ impl SerializeTuple for AttributeValueSeqTupleAndTupleStructSerializer {
     }
 }
 
-impl SerializeTupleStruct for AttributeValueSeqTupleAndTupleStructSerializer {
+impl<'a> SerializeTupleStruct for AttributeValueSeqTupleAndTupleStructSerializer<'a> {
     type Ok = AttributeValue;
     type Error = Error;
 
-    fn serialize_field<V: ?Sized>(&mut self, value: &V) -> Result<()>
+    fn serialize_field<V>(&mut self, value: &V) -> Result<()>
     where
-        V: Serialize,
+        V: ?Sized + Serialize,
     {
         self.serialize(value)
     }
@@ -499,25 +1527,45 @@ impl SerializeTupleStruct for AttributeValueSeqTupleAndTupleStructSerializer {
 
 // Serialize Rust struct values as the native AWS ATtributeValue map type, as
 // struct keys are already compatible with the requirement for a string-keyed
-// HashMap as the underlying store.
+// HashMap as the underlying store. Null fields may be omitted, as configured,
+// and marker fields are always omitted.
 
 use serde::ser::SerializeStruct;
 
-#[derive(Default)]
-pub struct AttributeValueStructSerializer {
+#[derive(new)]
+pub struct AttributeValueStructSerializer<'a> {
+    config: &'a SerializerConfig,
+    path: Path<'a>,
+    #[new(default)]
     values: HashMap<String, AttributeValue>,
 }
 
-impl SerializeStruct for AttributeValueStructSerializer {
+impl<'a> SerializeStruct for AttributeValueStructSerializer<'a> {
     type Ok = AttributeValue;
     type Error = Error;
 
-    fn serialize_field<V: ?Sized>(&mut self, key: &'static str, value: &V) -> Result<()>
+    fn serialize_field<V>(&mut self, key: &'static str, value: &V) -> Result<()>
     where
-        V: Serialize,
+        V: ?Sized + Serialize,
     {
-        self.values
-            .insert(key.to_owned(), value.serialize(AttributeValueSerializer)?);
+        if is_marker(value) {
+            return Ok(());
+        }
+
+        check_name(self.config, &self.path, key)?;
+
+        let value = value.serialize(AttributeValueSerializer::new(
+            self.config,
+            Path::Field(&self.path, key),
+        ))?;
+
+        if !self.config.omits(&value) {
+            if let (Path::Root, Some(on_attribute)) = (self.path, self.config.on_attribute) {
+                on_attribute(key, &value);
+            }
+
+            self.values.insert(key.to_owned(), value);
+        }
         Ok(())
     }
 
@@ -535,31 +1583,59 @@ impl SerializeStruct for AttributeValueStructSerializer {
 
 // Serialize Rust struct variant values using a singly-keyed map containing the
 // actual serialized variant data, where the key is the variant name. This maps
-// to the same convention used by the previously defined newtype variant.
+// to the same convention used by the previously defined newtype variant. Where
+// an item enum tag applies, the fields are stored directly, with the variant
+// name stored under the tag.
 
 use serde::ser::SerializeStructVariant;
 
 #[derive(new)]
-pub struct AttributeValueStructVariantSerializer {
+pub struct AttributeValueStructVariantSerializer<'a> {
+    config: &'a SerializerConfig,
+    path: Path<'a>,
+    tag: Option<&'a str>,
     #[new(default)]
     values: HashMap<String, AttributeValue>,
     variant: String,
 }
 
-impl SerializeStructVariant for AttributeValueStructVariantSerializer {
+impl<'a> SerializeStructVariant for AttributeValueStructVariantSerializer<'a> {
     type Ok = AttributeValue;
     type Error = Error;
 
-    fn serialize_field<V: ?Sized>(&mut self, field: &'static str, value: &V) -> Result<()>
+    fn serialize_field<V>(&mut self, field: &'static str, value: &V) -> Result<()>
     where
-        V: Serialize,
+        V: ?Sized + Serialize,
     {
-        self.values
-            .insert(field.to_owned(), value.serialize(AttributeValueSerializer)?);
+        if is_marker(value) {
+            return Ok(());
+        }
+
+        let variant = Path::Field(&self.path, &self.variant);
+
+        check_name(self.config, &variant, field)?;
+
+        let value = value.serialize(AttributeValueSerializer::new(
+            self.config,
+            Path::Field(&variant, field),
+        ))?;
+
+        if !self.config.omits(&value) {
+            self.values.insert(field.to_owned(), value);
+        }
         Ok(())
     }
 
-    fn end(self) -> Result<AttributeValue> {
+    fn end(mut self) -> Result<AttributeValue> {
+        if let Some(tag) = self.tag {
+            tag_item(&mut self.values, tag, &self.variant)?;
+
+            return Ok(AttributeValue {
+                m: Some(self.values),
+                ..AttributeValue::default()
+            });
+        }
+
         Ok(AttributeValue {
             m: Some(hashmap! {
                 self.variant => AttributeValue {
@@ -572,6 +1648,27 @@ impl SerializeStructVariant for AttributeValueStructVariantSerializer {
     }
 }
 
+// Store the variant name under the tag of a tagged variant, where the tag does
+// not conflict with a field of the variant.
+
+fn tag_item(m: &mut HashMap<String, AttributeValue>, tag: &str, variant: &str) -> Result<()> {
+    if m.contains_key(tag) {
+        return Err(Error::new(&format!(
+            "Tag {} Conflicts With A Field Of Variant {}",
+            tag, variant
+        )));
+    }
+
+    m.insert(
+        tag.to_owned(),
+        AttributeValue {
+            s: Some(variant.to_owned()),
+            ..AttributeValue::default()
+        },
+    );
+    Ok(())
+}
+
 // -----------------------------------------------------------------------------
 
 // Attribute Value Tuple Variant Serializer
@@ -583,21 +1680,27 @@ impl SerializeStructVariant for AttributeValueStructVariantSerializer {
 use serde::ser::SerializeTupleVariant;
 
 #[derive(new)]
-pub struct AttributeValueTupleVariantSerializer {
+pub struct AttributeValueTupleVariantSerializer<'a> {
+    config: &'a SerializerConfig,
+    path: Path<'a>,
     #[new(default)]
     values: Vec<AttributeValue>,
     variant: String,
 }
 
-impl SerializeTupleVariant for AttributeValueTupleVariantSerializer {
+impl<'a> SerializeTupleVariant for AttributeValueTupleVariantSerializer<'a> {
     type Ok = AttributeValue;
     type Error = Error;
 
-    fn serialize_field<V: ?Sized>(&mut self, value: &V) -> Result<()>
+    fn serialize_field<V>(&mut self, value: &V) -> Result<()>
     where
-        V: Serialize,
+        V: ?Sized + Serialize,
     {
-        self.values.push(value.serialize(AttributeValueSerializer)?);
+        let variant = Path::Field(&self.path, &self.variant);
+        let path = Path::Index(&variant, self.values.len());
+
+        self.values
+            .push(value.serialize(AttributeValueSerializer::new(self.config, path))?);
         Ok(())
     }
 
@@ -618,9 +1721,114 @@ impl SerializeTupleVariant for AttributeValueTupleVariantSerializer {
 
 // Attribute Value Serialization Functions
 
+use super::util::into_item;
+
+// Serialize a value to the AWS AttributeValue representation. As serialization
+// only requires a reference, to_attribute_value accepts borrowed values (as
+// &T is Serialize where T is), while to_attribute_value_ref makes borrowing
+// explicit, and also supports unsized values such as str and slices.
+
+// Serialization with non-default configuration is available through the
+// to_attribute_value_with_config function, and values which serialize to maps
+// (such as structs, or maps with string keys, where each entry of the map
+// becomes an attribute) can be serialized directly to items (as taken by the
+// AWS APIs) using the to_item functions.
+
+// Sequences of values can be serialized to items lazily using the
+// to_items_iter functions, which serialize each value as the returned iterator
+// is consumed (for example, when writing large numbers of items in batches),
+// rather than collecting all of the items first.
+
+// Values which serialize to maps can also be serialized as expression attribute
+// values (as taken by update, query and conditional APIs), where each attribute
+// name is given a prefix (typically ":") for use as a placeholder.
+
 pub fn to_attribute_value<T>(value: T) -> Result<AttributeValue>
 where
     T: Serialize,
 {
-    value.serialize(AttributeValueSerializer::new())
+    to_attribute_value_with_config(value, &SerializerConfig::default())
+}
+
+pub fn to_attribute_value_ref<T>(value: &T) -> Result<AttributeValue>
+where
+    T: ?Sized + Serialize,
+{
+    value.serialize(AttributeValueSerializer::new(
+        &SerializerConfig::default(),
+        Path::Root,
+    ))
+}
+
+pub fn to_attribute_value_with_config<T>(
+    value: T,
+    config: &SerializerConfig,
+) -> Result<AttributeValue>
+where
+    T: Serialize,
+{
+    value.serialize(AttributeValueSerializer::new(config, Path::Root))
+}
+
+pub fn to_item<T>(value: T) -> Result<HashMap<String, AttributeValue>>
+where
+    T: Serialize,
+{
+    to_item_with_config(value, &SerializerConfig::default())
+}
+
+pub fn to_item_with_config<T>(
+    value: T,
+    config: &SerializerConfig,
+) -> Result<HashMap<String, AttributeValue>>
+where
+    T: Serialize,
+{
+    to_attribute_value_with_config(value, config).and_then(into_item)
+}
+
+pub fn to_items_iter<T, I>(
+    values: I,
+) -> impl Iterator<Item = Result<HashMap<String, AttributeValue>>>
+where
+    T: Serialize,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().map(to_item)
+}
+
+pub fn to_items_iter_with_config<'a, T, I>(
+    values: I,
+    config: &'a SerializerConfig,
+) -> impl Iterator<Item = Result<HashMap<String, AttributeValue>>> + 'a
+where
+    T: Serialize,
+    I: IntoIterator<Item = T>,
+    I::IntoIter: 'a,
+{
+    values
+        .into_iter()
+        .map(move |value| to_item_with_config(value, config))
+}
+
+pub fn to_expression_values<T>(value: T, prefix: &str) -> Result<HashMap<String, AttributeValue>>
+where
+    T: Serialize,
+{
+    to_expression_values_with_config(value, prefix, &SerializerConfig::default())
+}
+
+pub fn to_expression_values_with_config<T>(
+    value: T,
+    prefix: &str,
+    config: &SerializerConfig,
+) -> Result<HashMap<String, AttributeValue>>
+where
+    T: Serialize,
+{
+    to_item_with_config(value, config).map(|item| {
+        item.into_iter()
+            .map(|(name, value)| (format!("{}{}", prefix, name), value))
+            .collect()
+    })
 }
@@ -0,0 +1,105 @@
+// Streams
+
+// The DynamoDB Streams API (and so the rusoto_dynamodbstreams crate) defines
+// its own AttributeValue type, structurally identical to the DynamoDB type but
+// a distinct Rust type. Conversions between the two types are provided, along
+// with serialization and deserialization functions targeting the Streams type
+// directly (converting via the DynamoDB type), available with the "streams"
+// feature.
+
+use super::{
+    de::{self, DeserializerConfig},
+    result::Result,
+    ser::{self, SerializerConfig},
+};
+use rusoto_dynamodb::AttributeValue;
+use rusoto_dynamodbstreams::AttributeValue as StreamsAttributeValue;
+use serde::{de::DeserializeOwned, Serialize};
+
+// -----------------------------------------------------------------------------
+
+// Conversions
+
+// The foreign types prevent implementing From, so conversion is provided by
+// simple functions, moving the values (and recursively converting any nested
+// list and map values).
+
+pub fn to_dynamodb(value: StreamsAttributeValue) -> AttributeValue {
+    AttributeValue {
+        b: value.b,
+        bool: value.bool,
+        bs: value.bs,
+        l: value.l.map(|l| l.into_iter().map(to_dynamodb).collect()),
+        m: value.m.map(|m| {
+            m.into_iter()
+                .map(|(name, value)| (name, to_dynamodb(value)))
+                .collect()
+        }),
+        n: value.n,
+        ns: value.ns,
+        null: value.null,
+        s: value.s,
+        ss: value.ss,
+    }
+}
+
+pub fn to_streams(value: AttributeValue) -> StreamsAttributeValue {
+    StreamsAttributeValue {
+        b: value.b,
+        bool: value.bool,
+        bs: value.bs,
+        l: value.l.map(|l| l.into_iter().map(to_streams).collect()),
+        m: value.m.map(|m| {
+            m.into_iter()
+                .map(|(name, value)| (name, to_streams(value)))
+                .collect()
+        }),
+        n: value.n,
+        ns: value.ns,
+        null: value.null,
+        s: value.s,
+        ss: value.ss,
+    }
+}
+
+// =============================================================================
+
+// Streams Attribute Value Serialization/Deserialization Functions
+
+// As the values are converted, deserialization can not borrow from the Streams
+// value, and so requires owned types. The converted value is deserialized as an
+// owned value, so strings, lists and maps are moved rather than copied again.
+
+pub fn to_attribute_value<T>(value: T) -> Result<StreamsAttributeValue>
+where
+    T: Serialize,
+{
+    to_attribute_value_with_config(value, &SerializerConfig::default())
+}
+
+pub fn to_attribute_value_with_config<T>(
+    value: T,
+    config: &SerializerConfig,
+) -> Result<StreamsAttributeValue>
+where
+    T: Serialize,
+{
+    ser::to_attribute_value_with_config(value, config).map(to_streams)
+}
+
+pub fn from_attribute_value<T>(value: &StreamsAttributeValue) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    from_attribute_value_with_config(value, &DeserializerConfig::default())
+}
+
+pub fn from_attribute_value_with_config<T>(
+    value: &StreamsAttributeValue,
+    config: &DeserializerConfig,
+) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    de::from_attribute_value_owned_with_config(to_dynamodb(value.clone()), config)
+}
@@ -0,0 +1,36 @@
+// TTL
+
+// Helpers for use with serde(with) to store a SystemTime as a number of whole
+// seconds since the Unix epoch, the form DynamoDB expects for the attribute
+// used as the time to live (TTL) of an item. Fractions of a second are
+// truncated when serializing (TTL has a resolution of seconds), and times
+// before the epoch are rejected. Values are deserialized from non-negative
+// integer numbers only.
+
+// #[serde(with = "serde_rusoto_dynamodb::ttl")]
+
+use serde::{
+    de::Error as SerdeDeError, ser::Error as SerdeSerError, Deserialize, Deserializer, Serializer,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub fn serialize<S>(value: &SystemTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value.duration_since(UNIX_EPOCH) {
+        Ok(duration) => serializer.serialize_u64(duration.as_secs()),
+        _ => Err(S::Error::custom("Time Before Unix Epoch Not Supported")),
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<SystemTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let seconds = u64::deserialize(deserializer)?;
+
+    UNIX_EPOCH
+        .checked_add(Duration::from_secs(seconds))
+        .ok_or_else(|| D::Error::custom(format!("Time {} Out Of Range", seconds)))
+}
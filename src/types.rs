@@ -0,0 +1,472 @@
+// Types
+
+// Wrapper types for values which need some specific handling when serialized
+// to (or deserialized from) the native AWS AttributeValue representation, while
+// still serializing conventionally with other Serde formats.
+
+use bytes::Bytes;
+use serde::{
+    de::{Deserialize, Deserializer, Error as SerdeDeError, SeqAccess, Visitor},
+    ser::{Serialize, Serializer},
+};
+use std::{
+    cell::RefCell,
+    convert::TryFrom,
+    fmt::{Formatter, Result as StdFmtResult},
+};
+
+// -----------------------------------------------------------------------------
+
+// Owned Bytes
+
+// Byte values are serialized by Serde as borrowed slices, and so are normally
+// copied in to the binary value of the AWS AttributeValue. For large binary
+// payloads this copy can be significant, so OwnedBytes takes ownership of the
+// bytes (converting from a Vec<u8> without copying) and hands a shared
+// reference to the underlying buffer to the serializer, which stores it in the
+// binary value directly.
+
+// The handoff is made through a thread local value, recognised by the
+// serializer through the name of the newtype struct the bytes are wrapped in.
+// Other serializers see a newtype struct containing bytes, and serialize it as
+// they would any other.
+
+pub(crate) const OWNED_BYTES: &str = "$serde_rusoto_dynamodb::OwnedBytes";
+
+thread_local! {
+    static OWNED: RefCell<Option<Bytes>> = const { RefCell::new(None) };
+}
+
+pub(crate) fn take_owned_bytes() -> Option<Bytes> {
+    OWNED.with(|owned| owned.borrow_mut().take())
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnedBytes(pub Bytes);
+
+impl OwnedBytes {
+    pub fn into_inner(self) -> Bytes {
+        self.0
+    }
+}
+
+impl From<Bytes> for OwnedBytes {
+    fn from(bytes: Bytes) -> Self {
+        OwnedBytes(bytes)
+    }
+}
+
+impl From<Vec<u8>> for OwnedBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        OwnedBytes(Bytes::from(bytes))
+    }
+}
+
+struct BytesRef<'a>(&'a [u8]);
+
+impl<'a> Serialize for BytesRef<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+impl Serialize for OwnedBytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        OWNED.with(|owned| *owned.borrow_mut() = Some(self.0.clone()));
+
+        let result = serializer.serialize_newtype_struct(OWNED_BYTES, &BytesRef(&self.0));
+
+        take_owned_bytes();
+        result
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct OwnedBytesVisitor;
+
+        impl<'de> Visitor<'de> for OwnedBytesVisitor {
+            type Value = OwnedBytes;
+
+            fn expecting(&self, f: &mut Formatter) -> StdFmtResult {
+                f.write_str("a byte array")
+            }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+            where
+                E: SerdeDeError,
+            {
+                Ok(OwnedBytes(Bytes::from(value)))
+            }
+
+            fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+            where
+                E: SerdeDeError,
+            {
+                Ok(OwnedBytes::from(value))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+
+                Ok(OwnedBytes::from(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(OwnedBytesVisitor)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Byte Array
+
+// Fixed size arrays are serialized by Serde as tuples, so an array of bytes
+// (such as a raw UUID) is normally stored as a list of numbers. ByteArray
+// serializes the array as bytes instead, so that it is stored as the native
+// binary type, and checks the length of the binary value when deserialized.
+// Other serializers see bytes, and serialize them as they would any other.
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ByteArray<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> ByteArray<N> {
+    pub fn into_inner(self) -> [u8; N] {
+        self.0
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for ByteArray<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        ByteArray(bytes)
+    }
+}
+
+impl<const N: usize> Serialize for ByteArray<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ByteArrayVisitor<const N: usize>;
+
+        fn length<E, const N: usize>(len: usize) -> E
+        where
+            E: SerdeDeError,
+        {
+            E::custom(format!(
+                "Binary Value Of Length {} Expected, Found Length {}",
+                N, len
+            ))
+        }
+
+        impl<'de, const N: usize> Visitor<'de> for ByteArrayVisitor<N> {
+            type Value = ByteArray<N>;
+
+            fn expecting(&self, f: &mut Formatter) -> StdFmtResult {
+                write!(f, "a byte array of length {}", N)
+            }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+            where
+                E: SerdeDeError,
+            {
+                match <[u8; N]>::try_from(value) {
+                    Ok(bytes) => Ok(ByteArray(bytes)),
+                    _ => Err(length::<E, N>(value.len())),
+                }
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut bytes = [0u8; N];
+                let mut len = 0;
+
+                while let Some(byte) = seq.next_element()? {
+                    if len < N {
+                        bytes[len] = byte;
+                    }
+
+                    len += 1;
+                }
+
+                match len {
+                    len if len == N => Ok(ByteArray(bytes)),
+                    len => Err(length::<A::Error, N>(len)),
+                }
+            }
+        }
+
+        deserializer.deserialize_bytes(ByteArrayVisitor)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Sets
+
+// Sequences are serialized as the native AWS AttributeValue list type by
+// default, as Serde does not distinguish sets from other sequences. The set
+// wrapper types store a collection (such as a HashSet or BTreeSet, though any
+// sequence will do) as the native string, number or binary set type instead,
+// where the elements serialize to strings, numbers or binary values
+// respectively. DynamoDB does not support empty sets, so serializing an empty
+// collection is an error.
+
+// As with OwnedBytes, the serializer and deserializer recognise the set types
+// through the name of the newtype struct the collection is wrapped in, and
+// other serializers see a newtype struct containing a sequence.
+
+use super::kind::AttributeKind;
+use std::marker::PhantomData;
+
+pub(crate) const BINARY_SET: &str = "$serde_rusoto_dynamodb::BinarySet";
+pub(crate) const NUMBER_SET: &str = "$serde_rusoto_dynamodb::NumberSet";
+pub(crate) const STRING_SET: &str = "$serde_rusoto_dynamodb::StringSet";
+
+pub(crate) fn set_kind(name: &str) -> Option<AttributeKind> {
+    match name {
+        BINARY_SET => Some(AttributeKind::BinarySet),
+        NUMBER_SET => Some(AttributeKind::NumberSet),
+        STRING_SET => Some(AttributeKind::StringSet),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BinarySet<T>(pub T);
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NumberSet<T>(pub T);
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StringSet<T>(pub T);
+
+impl<T> BinarySet<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> NumberSet<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> StringSet<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for BinarySet<T> {
+    fn from(values: T) -> Self {
+        BinarySet(values)
+    }
+}
+
+impl<T> From<T> for NumberSet<T> {
+    fn from(values: T) -> Self {
+        NumberSet(values)
+    }
+}
+
+impl<T> From<T> for StringSet<T> {
+    fn from(values: T) -> Self {
+        StringSet(values)
+    }
+}
+
+impl<T: Serialize> Serialize for BinarySet<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(BINARY_SET, &self.0)
+    }
+}
+
+impl<T: Serialize> Serialize for NumberSet<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(NUMBER_SET, &self.0)
+    }
+}
+
+impl<T: Serialize> Serialize for StringSet<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(STRING_SET, &self.0)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for BinarySet<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_newtype_struct(BINARY_SET, SetVisitor(PhantomData))
+            .map(BinarySet)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for NumberSet<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_newtype_struct(NUMBER_SET, SetVisitor(PhantomData))
+            .map(NumberSet)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for StringSet<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_newtype_struct(STRING_SET, SetVisitor(PhantomData))
+            .map(StringSet)
+    }
+}
+
+struct SetVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SetVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> StdFmtResult {
+        f.write_str("a set")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Set Helpers
+
+// Helpers for use with serde(with) to store a collection as the matching native
+// set type without changing the type of the field to one of the set wrapper
+// types, as with the wrapper types (so serializing an empty collection is an
+// error, and repeated elements are removed).
+
+// #[serde(with = "serde_rusoto_dynamodb::as_string_set")]
+
+macro_rules! set_helper {
+    ($module:ident, $set:ident) => {
+        pub mod $module {
+            use super::$set;
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: Serialize,
+                S: Serializer,
+            {
+                $set(value).serialize(serializer)
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: Deserialize<'de>,
+                D: Deserializer<'de>,
+            {
+                $set::deserialize(deserializer).map($set::into_inner)
+            }
+        }
+    };
+}
+
+set_helper!(as_binary_set, BinarySet);
+set_helper!(as_number_set, NumberSet);
+set_helper!(as_string_set, StringSet);
+
+// -----------------------------------------------------------------------------
+
+// Double Option
+
+// Nested options collapse when serialized, as both None and Some(None) are
+// represented by the native null value. DoubleOption distinguishes them (for
+// example, to tell a field which should be removed from one which should be
+// left unchanged) by wrapping the outer Some in a single element list, so that
+// None is serialized as null, Some(None) as a list containing null, and
+// Some(Some(value)) as a list containing the value. Other serializers see an
+// option of a single element tuple, and so represent each case distinctly in
+// the same way.
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DoubleOption<T>(pub Option<Option<T>>);
+
+impl<T> DoubleOption<T> {
+    pub fn into_inner(self) -> Option<Option<T>> {
+        self.0
+    }
+}
+
+impl<T> From<Option<Option<T>>> for DoubleOption<T> {
+    fn from(value: Option<Option<T>>) -> Self {
+        DoubleOption(value)
+    }
+}
+
+impl<T: Serialize> Serialize for DoubleOption<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match &self.0 {
+            Some(inner) => serializer.serialize_some(&(inner,)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for DoubleOption<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<(Option<T>,)>::deserialize(deserializer)
+            .map(|value| DoubleOption(value.map(|(inner,)| inner)))
+    }
+}
@@ -0,0 +1,412 @@
+// Utilities
+
+// Helper functions for working with AWS AttributeValue data directly, rather
+// than through serialization and deserialization.
+
+use rusoto_dynamodb::AttributeValue;
+use std::collections::HashMap;
+
+// -----------------------------------------------------------------------------
+
+// Item
+
+// Extract the map of an AttributeValue known to be a map (such as a serialized
+// struct) as an item, either by value or by reference, reporting the kind of
+// value found where the value is not a map.
+
+use super::{
+    kind::AttributeKind,
+    result::{Error, Result},
+};
+
+pub fn into_item(value: AttributeValue) -> Result<HashMap<String, AttributeValue>> {
+    match value {
+        AttributeValue { m: Some(m), .. } => Ok(m),
+        value => Err(map_expected(&value)),
+    }
+}
+
+pub fn as_item(value: &AttributeValue) -> Result<&HashMap<String, AttributeValue>> {
+    match value {
+        AttributeValue { m: Some(m), .. } => Ok(m),
+        value => Err(map_expected(value)),
+    }
+}
+
+fn map_expected(value: &AttributeValue) -> Error {
+    Error::new(&format!(
+        "Map Value Expected, Found {}",
+        AttributeKind::of(value)
+    ))
+}
+
+// -----------------------------------------------------------------------------
+
+// Item Size
+
+// Estimate the size of an item (or single value) as accounted by DynamoDB when
+// applying the 400 KB item size limit, following the documented rules:
+
+// - Attribute names count their UTF-8 length in bytes.
+// - Strings count their UTF-8 length in bytes, and binary values their length.
+// - Numbers count approximately 1 byte per two significant digits, plus 1 byte
+//   (leading and trailing zeroes are not significant).
+// - Booleans and nulls count 1 byte.
+// - Lists and maps count 3 bytes, plus 1 byte per element, plus the size of
+//   each element (including the names of map elements).
+// - Sets count the sum of the sizes of their elements.
+
+pub fn item_size(item: &HashMap<String, AttributeValue>) -> usize {
+    item.iter()
+        .map(|(name, value)| name.len() + attribute_value_size(value))
+        .sum()
+}
+
+pub fn attribute_value_size(value: &AttributeValue) -> usize {
+    match value {
+        AttributeValue { b: Some(b), .. } => b.len(),
+        AttributeValue { bool: Some(_), .. } => 1,
+        AttributeValue { bs: Some(bs), .. } => bs.iter().map(|b| b.len()).sum(),
+        AttributeValue { l: Some(l), .. } => {
+            3 + l.iter().map(|v| 1 + attribute_value_size(v)).sum::<usize>()
+        }
+        AttributeValue { m: Some(m), .. } => {
+            3 + m
+                .iter()
+                .map(|(name, v)| 1 + name.len() + attribute_value_size(v))
+                .sum::<usize>()
+        }
+        AttributeValue { n: Some(n), .. } => number_size(n),
+        AttributeValue { ns: Some(ns), .. } => ns.iter().map(|n| number_size(n)).sum(),
+        AttributeValue { null: Some(_), .. } => 1,
+        AttributeValue { s: Some(s), .. } => s.len(),
+        AttributeValue { ss: Some(ss), .. } => ss.iter().map(|s| s.len()).sum(),
+        _ => 0,
+    }
+}
+
+fn number_size(n: &str) -> usize {
+    let mantissa = n.split(['e', 'E']).next().unwrap_or_default();
+    let digits = mantissa
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+    let significant = digits.trim_start_matches('0').trim_end_matches('0');
+
+    significant.len().div_ceil(2) + 1
+}
+
+// -----------------------------------------------------------------------------
+
+// Debug Item
+
+// Render an item (or single value) in a compact, readable form for logging and
+// test output, such as { count: N42, id: S"abc", tags: SS[a,b] }, where each
+// value is prefixed by the DynamoDB type descriptor. Map entries are rendered
+// in key order, and maps or lists containing nested maps or lists are rendered
+// across multiple lines with indentation.
+
+use std::fmt::Write;
+
+pub fn debug_item(item: &HashMap<String, AttributeValue>) -> String {
+    let mut out = String::new();
+    write_map(&mut out, item, 0);
+    out
+}
+
+pub fn debug_attribute_value(value: &AttributeValue) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, 0);
+    out
+}
+
+fn write_value(out: &mut String, value: &AttributeValue, depth: usize) {
+    match value {
+        AttributeValue { b: Some(b), .. } => {
+            out.push('B');
+            write_hex(out, b);
+        }
+        AttributeValue { bool: Some(b), .. } => {
+            let _ = write!(out, "BOOL{}", b);
+        }
+        AttributeValue { bs: Some(bs), .. } => {
+            out.push_str("BS[");
+            for (i, b) in bs.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_hex(out, b);
+            }
+            out.push(']');
+        }
+        AttributeValue { l: Some(l), .. } => {
+            out.push('L');
+            write_list(out, l, depth);
+        }
+        AttributeValue { m: Some(m), .. } => {
+            out.push('M');
+            write_map(out, m, depth);
+        }
+        AttributeValue { n: Some(n), .. } => {
+            let _ = write!(out, "N{}", n);
+        }
+        AttributeValue { ns: Some(ns), .. } => {
+            let _ = write!(out, "NS[{}]", ns.join(","));
+        }
+        AttributeValue { null: Some(_), .. } => out.push_str("NULL"),
+        AttributeValue { s: Some(s), .. } => {
+            let _ = write!(out, "S{:?}", s);
+        }
+        AttributeValue { ss: Some(ss), .. } => {
+            let _ = write!(out, "SS[{}]", ss.join(","));
+        }
+        _ => out.push_str("EMPTY"),
+    }
+}
+
+fn write_map(out: &mut String, m: &HashMap<String, AttributeValue>, depth: usize) {
+    let mut entries = m.iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(name, _)| *name);
+
+    if entries.is_empty() {
+        out.push_str("{}");
+    } else if entries.iter().any(|(_, value)| is_compound(value)) {
+        out.push_str("{\n");
+        for (name, value) in entries {
+            write_indent(out, depth + 1);
+            let _ = write!(out, "{}: ", name);
+            write_value(out, value, depth + 1);
+            out.push_str(",\n");
+        }
+        write_indent(out, depth);
+        out.push('}');
+    } else {
+        out.push_str("{ ");
+        for (i, (name, value)) in entries.into_iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            let _ = write!(out, "{}: ", name);
+            write_value(out, value, depth);
+        }
+        out.push_str(" }");
+    }
+}
+
+fn write_list(out: &mut String, l: &[AttributeValue], depth: usize) {
+    if l.is_empty() {
+        out.push_str("[]");
+    } else if l.iter().any(is_compound) {
+        out.push_str("[\n");
+        for value in l {
+            write_indent(out, depth + 1);
+            write_value(out, value, depth + 1);
+            out.push_str(",\n");
+        }
+        write_indent(out, depth);
+        out.push(']');
+    } else {
+        out.push('[');
+        for (i, value) in l.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            write_value(out, value, depth);
+        }
+        out.push(']');
+    }
+}
+
+fn write_hex(out: &mut String, bytes: &[u8]) {
+    out.push('<');
+    for byte in bytes {
+        let _ = write!(out, "{:02x}", byte);
+    }
+    out.push('>');
+}
+
+fn write_indent(out: &mut String, depth: usize) {
+    out.push_str(&"  ".repeat(depth));
+}
+
+fn is_compound(value: &AttributeValue) -> bool {
+    value.l.is_some() || value.m.is_some()
+}
+
+// -----------------------------------------------------------------------------
+
+// Validation
+
+// Check that a value (such as one imported from an untrusted export) is well
+// formed before deserializing it, recursing in to lists, maps and sets and
+// reporting the first malformed value found along with its path. A value is
+// well formed where:
+
+// - Exactly one type field is set.
+// - Null values are true (DynamoDB rejects a null value of false).
+// - Numbers (including the elements of number sets) are finite numbers.
+// - Sets are not empty, and do not contain duplicate elements (compared as
+//   they are written, so number set elements such as 1 and 1.0 are distinct).
+
+use super::path::Path;
+use std::{collections::HashSet, hash::Hash};
+
+pub fn validate_attribute_value(value: &AttributeValue) -> Result<()> {
+    validate(value, &Path::Root)
+}
+
+fn validate(value: &AttributeValue, path: &Path) -> Result<()> {
+    let kinds = [
+        (value.b.is_some(), AttributeKind::Binary),
+        (value.bool.is_some(), AttributeKind::Bool),
+        (value.bs.is_some(), AttributeKind::BinarySet),
+        (value.l.is_some(), AttributeKind::List),
+        (value.m.is_some(), AttributeKind::Map),
+        (value.n.is_some(), AttributeKind::Number),
+        (value.ns.is_some(), AttributeKind::NumberSet),
+        (value.null.is_some(), AttributeKind::Null),
+        (value.s.is_some(), AttributeKind::String),
+        (value.ss.is_some(), AttributeKind::StringSet),
+    ]
+    .iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, kind)| kind.to_string())
+    .collect::<Vec<_>>();
+
+    match kinds.len() {
+        0 => return Err(path.error("Single Type Expected, Found None")),
+        1 => (),
+        _ => {
+            return Err(path.error(&format!(
+                "Single Type Expected, Found {}",
+                kinds.join(" And ")
+            )))
+        }
+    }
+
+    match value {
+        AttributeValue { bs: Some(bs), .. } => validate_set(bs, path),
+        AttributeValue { l: Some(l), .. } => l
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, value)| validate(value, &Path::Index(path, i))),
+        AttributeValue { m: Some(m), .. } => m
+            .iter()
+            .try_for_each(|(name, value)| validate(value, &Path::Field(path, name))),
+        AttributeValue { n: Some(n), .. } => validate_number(n, path),
+        AttributeValue { ns: Some(ns), .. } => {
+            validate_set(ns, path)?;
+            ns.iter()
+                .enumerate()
+                .try_for_each(|(i, n)| validate_number(n, &Path::Index(path, i)))
+        }
+        AttributeValue {
+            null: Some(false), ..
+        } => Err(path.error("Null Value Must Be True")),
+        AttributeValue { ss: Some(ss), .. } => validate_set(ss, path),
+        _ => Ok(()),
+    }
+}
+
+fn validate_number(n: &str, path: &Path) -> Result<()> {
+    match n.parse::<f64>() {
+        Ok(f) if f.is_finite() => Ok(()),
+        _ => Err(path.error(&format!("Number Expected, Found {:?}", n))),
+    }
+}
+
+fn validate_set<T: Eq + Hash>(elements: &[T], path: &Path) -> Result<()> {
+    let mut seen = HashSet::with_capacity(elements.len());
+
+    match elements {
+        [] => Err(path.error("Set Must Not Be Empty")),
+        _ if elements.iter().all(|element| seen.insert(element)) => Ok(()),
+        _ => Err(path.error("Set Must Not Contain Duplicates")),
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Builders
+
+// Construct values (such as test fixtures, or values for expressions) without
+// spelling out each AttributeValue in full. Numbers are given either as
+// integers or as strings (which are used as written, and may be checked with
+// validate_attribute_value). Floats are not accepted, as not every float is a
+// valid number (such as NaN or infinity), and as Display would write a float
+// such as 1.0 as "1", unlike when serialized. Maps and lists are built from any
+// iterator of entries or values.
+
+// util::av_map(vec![
+//     ("id".to_owned(), util::av_s("abc")),
+//     ("scores".to_owned(), util::av_list(vec![util::av_n(1), util::av_n("2.5")])),
+// ])
+
+use bytes::Bytes;
+
+pub fn av_b(b: impl Into<Bytes>) -> AttributeValue {
+    AttributeValue {
+        b: Some(b.into()),
+        ..AttributeValue::default()
+    }
+}
+
+pub fn av_bool(b: bool) -> AttributeValue {
+    AttributeValue {
+        bool: Some(b),
+        ..AttributeValue::default()
+    }
+}
+
+pub fn av_list(items: impl IntoIterator<Item = AttributeValue>) -> AttributeValue {
+    AttributeValue {
+        l: Some(items.into_iter().collect()),
+        ..AttributeValue::default()
+    }
+}
+
+pub fn av_map(entries: impl IntoIterator<Item = (String, AttributeValue)>) -> AttributeValue {
+    AttributeValue {
+        m: Some(entries.into_iter().collect()),
+        ..AttributeValue::default()
+    }
+}
+
+pub trait Number {
+    fn into_number(self) -> String;
+}
+
+macro_rules! number {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                fn into_number(self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, &str, String);
+
+pub fn av_n(n: impl Number) -> AttributeValue {
+    AttributeValue {
+        n: Some(n.into_number()),
+        ..AttributeValue::default()
+    }
+}
+
+pub fn av_null() -> AttributeValue {
+    AttributeValue {
+        null: Some(true),
+        ..AttributeValue::default()
+    }
+}
+
+pub fn av_s(s: impl Into<String>) -> AttributeValue {
+    AttributeValue {
+        s: Some(s.into()),
+        ..AttributeValue::default()
+    }
+}
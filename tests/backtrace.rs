@@ -0,0 +1,23 @@
+// Backtrace
+
+// Backtrace capture depends on the environment (read once, when the first
+// backtrace is captured), so the backtrace tests run as a separate test binary
+// which enables capture before any error is created.
+
+#![cfg(feature = "backtrace")]
+
+use rusoto_dynamodb::AttributeValue;
+use std::backtrace::BacktraceStatus;
+
+#[test]
+fn error_backtrace() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    let error = serde_rusoto_dynamodb::from_attribute_value::<u8>(&AttributeValue {
+        s: Some("one".to_owned()),
+        ..AttributeValue::default()
+    })
+    .unwrap_err();
+
+    assert_eq!(error.backtrace().status(), BacktraceStatus::Captured);
+}
@@ -1,7 +1,11 @@
 use rusoto_dynamodb::AttributeValue;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_rusoto_dynamodb::result::Error;
-use std::{cmp::PartialEq, fmt::Debug};
+use serde_rusoto_dynamodb::util::{av_b, av_list, av_map, av_n, av_null, av_s};
+use serde_rusoto_dynamodb::{
+    result::Error, BinaryRepresentation, BinarySet, ByteArray, Coercion, DeserializerConfig,
+    DoubleOption, NumberSet, OwnedBytes, SerializerConfig, StringSet,
+};
+use std::{cmp::PartialEq, collections::HashMap, fmt::Debug};
 
 // Helpers
 
@@ -9,14 +13,35 @@ fn roundtrip<T>(value: &T, expected: &AttributeValue)
 where
     T: Debug + DeserializeOwned + PartialEq + Serialize,
 {
-    match serde_rusoto_dynamodb::to_attribute_value(&value) {
+    roundtrip_with_config(
+        value,
+        expected,
+        &SerializerConfig::default(),
+        &DeserializerConfig::default(),
+    )
+}
+
+fn roundtrip_with_config<T>(
+    value: &T,
+    expected: &AttributeValue,
+    ser_config: &SerializerConfig,
+    de_config: &DeserializerConfig,
+) where
+    T: Debug + DeserializeOwned + PartialEq + Serialize,
+{
+    match serde_rusoto_dynamodb::to_attribute_value_with_config(value, ser_config) {
         Ok(serialized) => {
             assert_eq!(&serialized, expected);
-            match serde_rusoto_dynamodb::from_attribute_value::<T>(&serialized) {
+            match serde_rusoto_dynamodb::from_attribute_value_with_config::<T>(
+                &serialized,
+                de_config,
+            ) {
                 Ok(deserialized) => {
                     assert_eq!(&deserialized, value);
                 }
-                Err(Error { message }) => panic!("Serialization failed with message: {}", message),
+                Err(Error { message }) => {
+                    panic!("Serialization failed with message: {}", message)
+                }
             }
         }
         Err(Error { message }) => panic!("Serialization failed with message: {}", message),
@@ -30,6 +55,8 @@ mod roundtrip {
 
     use super::*;
     use maplit::hashmap;
+    use serde_repr::{Deserialize_repr, Serialize_repr};
+    use std::net::{IpAddr, SocketAddr};
 
     // Boolean Values
 
@@ -129,6 +156,85 @@ mod roundtrip {
         );
     }
 
+    #[test]
+    fn roundtrip_numeric_128() {
+        use std::collections::BTreeSet;
+
+        roundtrip(
+            &u128::MAX,
+            &AttributeValue {
+                n: Some(u128::MAX.to_string()),
+                ..AttributeValue::default()
+            },
+        );
+        roundtrip(
+            &NumberSet(BTreeSet::from([i128::MIN, i128::MAX - 1, i128::MAX])),
+            &AttributeValue {
+                ns: Some(vec![
+                    "-170141183460469231731687303715884105728".to_owned(),
+                    "170141183460469231731687303715884105726".to_owned(),
+                    "170141183460469231731687303715884105727".to_owned(),
+                ]),
+                ..AttributeValue::default()
+            },
+        );
+    }
+
+    // Negative numbers round-trip as numbers and as the elements of number
+    // sets. Negative zero is written as "-0.0" (or "-0" where float integers
+    // are trimmed), and keeps its sign when deserialized as a float.
+
+    #[test]
+    fn roundtrip_numeric_negative() {
+        roundtrip(&-42i64, &av_n("-42"));
+        roundtrip(&-1.5f64, &av_n("-1.5"));
+        roundtrip(&-0.0f64, &av_n("-0.0"));
+        roundtrip(
+            &NumberSet(vec![-1.5f64, -2.0]),
+            &AttributeValue {
+                ns: Some(vec!["-1.5".to_owned(), "-2.0".to_owned()]),
+                ..AttributeValue::default()
+            },
+        );
+        roundtrip_with_config(
+            &-0.0f64,
+            &av_n("-0"),
+            &SerializerConfig {
+                trim_float_integers: true,
+                ..SerializerConfig::default()
+            },
+            &DeserializerConfig::default(),
+        );
+
+        for n in ["-0.0", "-0"] {
+            assert!(serde_rusoto_dynamodb::from_attribute_value::<f64>(&av_n(n))
+                .unwrap()
+                .is_sign_negative());
+        }
+    }
+
+    // Non-zero integers round-trip as their inner integer, while zero is
+    // rejected by the non-zero type itself.
+
+    #[test]
+    fn roundtrip_numeric_non_zero() {
+        use std::num::{NonZeroI32, NonZeroU64};
+
+        roundtrip(
+            &NonZeroU64::new(u64::MAX).unwrap(),
+            &av_n("18446744073709551615"),
+        );
+        roundtrip(&NonZeroI32::new(-1).unwrap(), &av_n("-1"));
+        roundtrip(&NonZeroU64::new(1), &av_n("1"));
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<NonZeroU64>(&av_n("0")),
+            Err(Error::new(
+                "invalid value: integer `0`, expected a nonzero u64"
+            ))
+        );
+    }
+
     // Char Values
 
     #[test]
@@ -142,8 +248,30 @@ mod roundtrip {
         );
     }
 
+    #[test]
+    fn serialize_character_multibyte() {
+        roundtrip(
+            &'👍',
+            &AttributeValue {
+                s: Some("👍".to_owned()),
+                ..AttributeValue::default()
+            },
+        );
+    }
+
     // String Values
 
+    #[test]
+    fn serialize_string_multibyte() {
+        roundtrip(
+            &"👍 e\u{301}".to_owned(),
+            &AttributeValue {
+                s: Some("👍 e\u{301}".to_owned()),
+                ..AttributeValue::default()
+            },
+        );
+    }
+
     #[test]
     fn serialize_string() {
         roundtrip(
@@ -234,6 +362,228 @@ mod roundtrip {
         );
     }
 
+    #[test]
+    fn roundtrip_struct_renamed_fields() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Test {
+            first_name: String,
+            #[serde(rename = "AGE")]
+            age_in_years: i32,
+        }
+
+        roundtrip(
+            &Test {
+                first_name: "hello".to_owned(),
+                age_in_years: 1,
+            },
+            &AttributeValue {
+                m: Some(hashmap! {
+                    "firstName".to_owned() => AttributeValue {
+                        s: Some("hello".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                    "AGE".to_owned() => AttributeValue {
+                        n: Some("1".to_owned()),
+                        ..AttributeValue::default()
+                    }
+                }),
+                ..AttributeValue::default()
+            },
+        );
+    }
+
+    #[test]
+    fn roundtrip_nested_struct() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Outer {
+            name: String,
+            inner: Inner,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Inner {
+            leaves: Vec<Leaf>,
+            tags: HashMap<String, HashMap<String, i32>>,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Leaf {
+            id: u32,
+        }
+
+        roundtrip(
+            &Outer {
+                name: "outer".to_owned(),
+                inner: Inner {
+                    leaves: vec![Leaf { id: 1 }, Leaf { id: 2 }],
+                    tags: hashmap! {
+                        "a".to_owned() => hashmap! {
+                            "b".to_owned() => 3,
+                        },
+                        "c".to_owned() => HashMap::new(),
+                    },
+                },
+            },
+            &av_map(hashmap! {
+                "name".to_owned() => AttributeValue {
+                    s: Some("outer".to_owned()),
+                    ..AttributeValue::default()
+                },
+                "inner".to_owned() => av_map(hashmap! {
+                    "leaves".to_owned() => AttributeValue {
+                        l: Some(vec![
+                            av_map(hashmap! { "id".to_owned() => av_n("1") }),
+                            av_map(hashmap! { "id".to_owned() => av_n("2") }),
+                        ]),
+                        ..AttributeValue::default()
+                    },
+                    "tags".to_owned() => av_map(hashmap! {
+                        "a".to_owned() => av_map(hashmap! { "b".to_owned() => av_n("3") }),
+                        "c".to_owned() => av_map(HashMap::new()),
+                    }),
+                }),
+            }),
+        );
+    }
+
+    // Flattened numeric maps are buffered by serde as untyped content, so the
+    // numbers are deserialized as any. Numbers with fractional parts (and
+    // floats serialized with a trailing ".0") are deserialized as floats, and
+    // integers are accepted as floats by the target map.
+
+    #[test]
+    fn roundtrip_flatten_numeric_map() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Reading {
+            sensor: String,
+            count: u32,
+            #[serde(flatten)]
+            metrics: HashMap<String, f64>,
+        }
+
+        let reading = Reading {
+            sensor: "north".to_owned(),
+            count: 3,
+            metrics: hashmap! {
+                "humidity".to_owned() => 40.0,
+                "pressure".to_owned() => -0.25,
+                "temperature".to_owned() => 21.5,
+            },
+        };
+
+        roundtrip(
+            &reading,
+            &av_map(hashmap! {
+                "sensor".to_owned() => AttributeValue {
+                    s: Some("north".to_owned()),
+                    ..AttributeValue::default()
+                },
+                "count".to_owned() => av_n("3"),
+                "humidity".to_owned() => av_n("40.0"),
+                "pressure".to_owned() => av_n("-0.25"),
+                "temperature".to_owned() => av_n("21.5"),
+            }),
+        );
+
+        roundtrip_with_config(
+            &reading,
+            &av_map(hashmap! {
+                "sensor".to_owned() => AttributeValue {
+                    s: Some("north".to_owned()),
+                    ..AttributeValue::default()
+                },
+                "count".to_owned() => av_n("3"),
+                "humidity".to_owned() => av_n("40"),
+                "pressure".to_owned() => av_n("-0.25"),
+                "temperature".to_owned() => av_n("21.5"),
+            }),
+            &SerializerConfig {
+                trim_float_integers: true,
+                ..SerializerConfig::default()
+            },
+            &DeserializerConfig::default(),
+        );
+    }
+
+    #[test]
+    fn roundtrip_deeply_nested_map() {
+        type Nested = HashMap<String, HashMap<String, HashMap<String, Vec<i32>>>>;
+
+        let value: Nested = hashmap! {
+            "x".to_owned() => hashmap! {
+                "y".to_owned() => hashmap! {
+                    "z".to_owned() => vec![1, 2],
+                },
+            },
+        };
+
+        let av_m = |key: &str, value: AttributeValue| AttributeValue {
+            m: Some(hashmap! { key.to_owned() => value }),
+            ..AttributeValue::default()
+        };
+
+        roundtrip(
+            &value,
+            &av_m(
+                "x",
+                av_m(
+                    "y",
+                    av_m(
+                        "z",
+                        AttributeValue {
+                            l: Some(vec![av_n("1"), av_n("2")]),
+                            ..AttributeValue::default()
+                        },
+                    ),
+                ),
+            ),
+        );
+    }
+
+    #[test]
+    fn roundtrip_map_of_lists_of_structs() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Item {
+            name: String,
+            tags: Option<Vec<String>>,
+        }
+
+        let value: HashMap<String, Vec<Item>> = hashmap! {
+            "a".to_owned() => vec![
+                Item {
+                    name: "x".to_owned(),
+                    tags: Some(vec!["t".to_owned()]),
+                },
+                Item {
+                    name: "y".to_owned(),
+                    tags: None,
+                },
+            ],
+            "b".to_owned() => vec![],
+        };
+
+        roundtrip(
+            &value,
+            &av_map(hashmap! {
+                "a".to_owned() => av_list(vec![
+                    av_map(hashmap! {
+                        "name".to_owned() => av_s("x"),
+                        "tags".to_owned() => av_list(vec![av_s("t")]),
+                    }),
+                    av_map(hashmap! {
+                        "name".to_owned() => av_s("y"),
+                        "tags".to_owned() => AttributeValue {
+                            null: Some(true),
+                            ..AttributeValue::default()
+                        },
+                    }),
+                ]),
+                "b".to_owned() => av_list(vec![]),
+            }),
+        );
+    }
+
     #[test]
     fn serialize_tuple() {
         roundtrip(
@@ -255,28 +605,5172 @@ mod roundtrip {
     }
 
     #[test]
-    fn serialize_unit() {
+    fn serialize_tuple_heterogeneous() {
+        roundtrip(
+            &("hello".to_owned(), 37, true),
+            &AttributeValue {
+                l: Some(vec![
+                    AttributeValue {
+                        s: Some("hello".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                    AttributeValue {
+                        n: Some("37".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                    AttributeValue {
+                        bool: Some(true),
+                        ..AttributeValue::default()
+                    },
+                ]),
+                ..AttributeValue::default()
+            },
+        )
+    }
+
+    // Transparent structs are serialized by serde exactly as their single field
+    // (without passing through the newtype struct methods), and so have no
+    // wrapper, whether at the top level or as fields.
+
+    #[test]
+    fn roundtrip_transparent() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        #[serde(transparent)]
+        struct Name(String);
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        #[serde(transparent)]
+        struct Scores {
+            scores: Vec<i32>,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Player {
+            name: Name,
+            scores: Scores,
+        }
+
+        let av_name = AttributeValue {
+            s: Some("alice".to_owned()),
+            ..AttributeValue::default()
+        };
+        let av_scores = AttributeValue {
+            l: Some(vec![av_n("1"), av_n("-2")]),
+            ..AttributeValue::default()
+        };
+
+        roundtrip(&Name("alice".to_owned()), &av_name);
+        roundtrip(
+            &Scores {
+                scores: vec![1, -2],
+            },
+            &av_scores,
+        );
+        roundtrip(
+            &Player {
+                name: Name("alice".to_owned()),
+                scores: Scores {
+                    scores: vec![1, -2],
+                },
+            },
+            &AttributeValue {
+                m: Some(hashmap! {
+                    "name".to_owned() => av_name,
+                    "scores".to_owned() => av_scores,
+                }),
+                ..AttributeValue::default()
+            },
+        );
+    }
+
+    // Tuples of each arity are distinct: the empty tuple is unit (and so null),
+    // while a single element tuple is a list of one element, rather than being
+    // treated as a newtype of its element.
+
+    #[test]
+    fn roundtrip_tuple_arity() {
         roundtrip(
             &(),
             &AttributeValue {
                 null: Some(true),
                 ..AttributeValue::default()
             },
-        )
+        );
+        roundtrip(
+            &(1,),
+            &AttributeValue {
+                l: Some(vec![av_n("1")]),
+                ..AttributeValue::default()
+            },
+        );
+        roundtrip(
+            &(1, 2),
+            &AttributeValue {
+                l: Some(vec![av_n("1"), av_n("2")]),
+                ..AttributeValue::default()
+            },
+        );
     }
 
-    // #[test]
-    // fn serialize_unit_variant() {
-    //     #[derive(Serialize)]
-    //     enum Test {
-    //         Unit,
-    //     }
+    // Network Address Values
 
-    //     let test: Test = Test::Unit;
+    // Network addresses round-trip through the readable string form when human
+    // readable (the default), and through the compact Serde form (octets as
+    // binary, keyed by address family) when not.
 
-    //     assert_eq!(
-    //         serde_rusoto_dynamodb::to_attribute_value(test),
-    //         Ok(av_s("Unit"))
-    //     );
-    // }
+    #[test]
+    fn serialize_network_address_human_readable() {
+        for address in &["192.168.0.1", "2001:db8::1"] {
+            roundtrip(
+                &address.parse::<IpAddr>().unwrap(),
+                &AttributeValue {
+                    s: Some((*address).to_owned()),
+                    ..AttributeValue::default()
+                },
+            );
+        }
+
+        for address in &["192.168.0.1:8080", "[2001:db8::1]:8080"] {
+            roundtrip(
+                &address.parse::<SocketAddr>().unwrap(),
+                &AttributeValue {
+                    s: Some((*address).to_owned()),
+                    ..AttributeValue::default()
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn serialize_network_address_compact() {
+        let ser_config = SerializerConfig {
+            human_readable: false,
+            ..SerializerConfig::default()
+        };
+        let de_config = DeserializerConfig {
+            human_readable: false,
+            ..DeserializerConfig::default()
+        };
+
+        let v4 = AttributeValue {
+            b: Some(vec![192, 168, 0, 1].into()),
+            ..AttributeValue::default()
+        };
+        let v6 = AttributeValue {
+            b: Some(vec![0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1].into()),
+            ..AttributeValue::default()
+        };
+
+        roundtrip_with_config(
+            &"192.168.0.1".parse::<IpAddr>().unwrap(),
+            &AttributeValue {
+                m: Some(hashmap! { "V4".to_owned() => v4.clone() }),
+                ..AttributeValue::default()
+            },
+            &ser_config,
+            &de_config,
+        );
+        roundtrip_with_config(
+            &"2001:db8::1".parse::<IpAddr>().unwrap(),
+            &AttributeValue {
+                m: Some(hashmap! { "V6".to_owned() => v6.clone() }),
+                ..AttributeValue::default()
+            },
+            &ser_config,
+            &de_config,
+        );
+        roundtrip_with_config(
+            &"192.168.0.1:8080".parse::<SocketAddr>().unwrap(),
+            &AttributeValue {
+                m: Some(hashmap! {
+                    "V4".to_owned() => AttributeValue {
+                        l: Some(vec![v4, av_n(8080)]),
+                        ..AttributeValue::default()
+                    }
+                }),
+                ..AttributeValue::default()
+            },
+            &ser_config,
+            &de_config,
+        );
+        roundtrip_with_config(
+            &"[2001:db8::1]:8080".parse::<SocketAddr>().unwrap(),
+            &AttributeValue {
+                m: Some(hashmap! {
+                    "V6".to_owned() => AttributeValue {
+                        l: Some(vec![v6, av_n(8080)]),
+                        ..AttributeValue::default()
+                    }
+                }),
+                ..AttributeValue::default()
+            },
+            &ser_config,
+            &de_config,
+        );
+
+        // Other tuples and arrays of u8 (which Serde serializes as it does the
+        // octets of an address) are unaffected, while the octets are limited
+        // in size as any other binary value.
+
+        roundtrip_with_config(
+            &(1u8, 2u8),
+            &av_list(vec![av_n(1), av_n(2)]),
+            &ser_config,
+            &de_config,
+        );
+        roundtrip_with_config(
+            &[1u8, 2, 3, 4],
+            &av_list(vec![av_n(1), av_n(2), av_n(3), av_n(4)]),
+            &ser_config,
+            &de_config,
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                "2001:db8::1".parse::<IpAddr>().unwrap(),
+                &SerializerConfig {
+                    max_value_bytes: Some(4),
+                    ..ser_config.clone()
+                }
+            ),
+            Err(Error::new(
+                "at V6: Value Of 16 Bytes Exceeds Maximum Of 4 Bytes"
+            ))
+        );
+    }
+
+    // Empty Values
+
+    #[test]
+    fn roundtrip_empty() {
+        roundtrip(
+            &Vec::<i32>::new(),
+            &AttributeValue {
+                l: Some(vec![]),
+                ..AttributeValue::default()
+            },
+        );
+        roundtrip(
+            &HashMap::<String, i32>::new(),
+            &AttributeValue {
+                m: Some(HashMap::new()),
+                ..AttributeValue::default()
+            },
+        );
+        roundtrip(
+            &String::new(),
+            &AttributeValue {
+                s: Some(String::new()),
+                ..AttributeValue::default()
+            },
+        );
+    }
+
+    // Set Values
+
+    #[test]
+    fn roundtrip_sets() {
+        use std::collections::BTreeSet;
+
+        roundtrip(
+            &StringSet(BTreeSet::from(["a".to_owned(), "b".to_owned()])),
+            &AttributeValue {
+                ss: Some(vec!["a".to_owned(), "b".to_owned()]),
+                ..AttributeValue::default()
+            },
+        );
+        roundtrip(
+            &NumberSet(BTreeSet::from([-1i64, 2])),
+            &AttributeValue {
+                ns: Some(vec!["-1".to_owned(), "2".to_owned()]),
+                ..AttributeValue::default()
+            },
+        );
+        roundtrip(
+            &BinarySet(vec![
+                OwnedBytes::from(vec![0u8]),
+                OwnedBytes::from(vec![1u8]),
+            ]),
+            &AttributeValue {
+                bs: Some(vec![vec![0u8].into(), vec![1u8].into()]),
+                ..AttributeValue::default()
+            },
+        );
+    }
+
+    // Set Helpers
+
+    #[test]
+    fn roundtrip_set_helpers() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            #[serde(with = "serde_rusoto_dynamodb::as_binary_set")]
+            keys: Vec<serde_bytes::ByteBuf>,
+            #[serde(with = "serde_rusoto_dynamodb::as_number_set")]
+            scores: Vec<i32>,
+            #[serde(with = "serde_rusoto_dynamodb::as_string_set")]
+            tags: Vec<String>,
+        }
+
+        roundtrip(
+            &Test {
+                keys: vec![serde_bytes::ByteBuf::from(vec![0u8])],
+                scores: vec![-1, 2],
+                tags: vec!["a".to_owned(), "b".to_owned()],
+            },
+            &AttributeValue {
+                m: Some(hashmap! {
+                    "keys".to_owned() => AttributeValue {
+                        bs: Some(vec![vec![0u8].into()]),
+                        ..AttributeValue::default()
+                    },
+                    "scores".to_owned() => AttributeValue {
+                        ns: Some(vec!["-1".to_owned(), "2".to_owned()]),
+                        ..AttributeValue::default()
+                    },
+                    "tags".to_owned() => AttributeValue {
+                        ss: Some(vec!["a".to_owned(), "b".to_owned()]),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            },
+        );
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Test {
+                keys: vec![serde_bytes::ByteBuf::from(vec![0u8])],
+                scores: vec![1],
+                tags: vec![],
+            }),
+            Err(Error::new("at tags: String Set Must Not Be Empty"))
+        );
+
+        // Repeated elements are removed (keeping the first of each), as
+        // DynamoDB rejects sets containing duplicates.
+
+        let value = serde_rusoto_dynamodb::to_attribute_value(Test {
+            keys: vec![serde_bytes::ByteBuf::from(vec![0u8]); 2],
+            scores: vec![2, -1, 2],
+            tags: vec!["b".to_owned(), "a".to_owned(), "b".to_owned()],
+        })
+        .unwrap();
+
+        assert_eq!(
+            value,
+            av_map(hashmap! {
+                "keys".to_owned() => AttributeValue {
+                    bs: Some(vec![vec![0u8].into()]),
+                    ..AttributeValue::default()
+                },
+                "scores".to_owned() => AttributeValue {
+                    ns: Some(vec!["2".to_owned(), "-1".to_owned()]),
+                    ..AttributeValue::default()
+                },
+                "tags".to_owned() => AttributeValue {
+                    ss: Some(vec!["b".to_owned(), "a".to_owned()]),
+                    ..AttributeValue::default()
+                },
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::util::validate_attribute_value(&value),
+            Ok(())
+        );
+    }
+
+    // Byte Arrays
+
+    #[test]
+    fn roundtrip_byte_array() {
+        let bytes = [0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 255];
+
+        roundtrip(&ByteArray(bytes), &av_b(bytes.to_vec()));
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<ByteArray<16>>(&av_b(
+                bytes[..15].to_vec()
+            )),
+            Err(Error::new(
+                "Binary Value Of Length 16 Expected, Found Length 15"
+            ))
+        );
+    }
+
+    // Borrowed Bytes
+
+    // Borrowed byte slices (such as Cow<[u8]>) are stored as binary where they
+    // are serialized as bytes (as with serde_bytes), and may be borrowed from
+    // the binary value when deserialized.
+
+    #[test]
+    fn roundtrip_cow_bytes() {
+        use std::borrow::Cow;
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test<'a> {
+            #[serde(borrow, with = "serde_bytes")]
+            a: Cow<'a, [u8]>,
+        }
+
+        let value = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    b: Some(vec![1u8, 2, 3].into()),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        let test = serde_rusoto_dynamodb::from_attribute_value::<Test>(&value).unwrap();
+
+        assert!(matches!(test.a, Cow::Borrowed([1, 2, 3])));
+        assert_eq!(serde_rusoto_dynamodb::to_attribute_value(&test), Ok(value));
+    }
+
+    // Marker Values
+
+    #[test]
+    fn roundtrip_phantom_data() {
+        use std::marker::PhantomData;
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test<T> {
+            a: i32,
+            #[serde(default)]
+            marker: PhantomData<T>,
+        }
+
+        let test = Test::<String> {
+            a: 1,
+            marker: PhantomData,
+        };
+        let expected = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    n: Some("1".to_owned()),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        roundtrip(&test, &expected);
+        roundtrip_with_config(
+            &test,
+            &expected,
+            &SerializerConfig {
+                omit_none: true,
+                ..SerializerConfig::default()
+            },
+            &DeserializerConfig::default(),
+        );
+    }
+
+    // Markers are recognised however they are referred to (such as by
+    // reference), while other unit structs are serialized as null.
+
+    #[test]
+    fn serialize_phantom_data_reference() {
+        use std::marker::PhantomData;
+
+        #[derive(Serialize)]
+        struct Unit;
+
+        #[derive(Serialize)]
+        struct Test<'a> {
+            marker: &'a PhantomData<u8>,
+            unit: Unit,
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Test {
+                marker: &PhantomData,
+                unit: Unit,
+            }),
+            Ok(AttributeValue {
+                m: Some(hashmap! {
+                    "unit".to_owned() => AttributeValue {
+                        null: Some(true),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
+    // Result Values
+
+    #[test]
+    fn roundtrip_result() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            result: Result<String, String>,
+        }
+
+        roundtrip(
+            &Test {
+                result: Ok("done".to_owned()),
+            },
+            &av_map(hashmap! {
+                "result".to_owned() => av_map(hashmap! { "Ok".to_owned() => av_s("done") }),
+            }),
+        );
+        roundtrip(
+            &Test {
+                result: Err("failed".to_owned()),
+            },
+            &av_map(hashmap! {
+                "result".to_owned() => av_map(hashmap! { "Err".to_owned() => av_s("failed") }),
+            }),
+        );
+    }
+
+    // Range Values
+
+    // Ranges are serialized by Serde as structs, and so are stored as maps of
+    // their start and end fields.
+
+    #[test]
+    fn roundtrip_range() {
+        use std::ops::{Range, RangeInclusive};
+
+        let expected = AttributeValue {
+            m: Some(hashmap! {
+                "start".to_owned() => av_n("-5"),
+                "end".to_owned() => av_n("10"),
+            }),
+            ..AttributeValue::default()
+        };
+
+        roundtrip::<Range<i64>>(&(-5..10), &expected);
+        roundtrip::<RangeInclusive<i64>>(&(-5..=10), &expected);
+    }
+
+    // Padded Numbers
+
+    #[test]
+    fn roundtrip_padded_number() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            #[serde(
+                serialize_with = "serde_rusoto_dynamodb::padded_number::serialize::<10, _, _>",
+                deserialize_with = "serde_rusoto_dynamodb::padded_number::deserialize::<10, _, _>"
+            )]
+            id: u64,
+        }
+
+        roundtrip(
+            &Test { id: 42 },
+            &av_map(hashmap! { "id".to_owned() => av_s("0000000042") }),
+        );
+        roundtrip(
+            &Test { id: 9_999_999_999 },
+            &av_map(hashmap! { "id".to_owned() => av_s("9999999999") }),
+        );
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Test { id: 10_000_000_000 }),
+            Err(Error::new("Number 10000000000 Exceeds Width Of 10 Digits"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&av_map(
+                hashmap! { "id".to_owned() => av_s("-42") }
+            )),
+            Err(Error::new("Padded Number Expected, Found -42"))
+        );
+    }
+
+    // TTL
+
+    #[test]
+    fn roundtrip_ttl() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            #[serde(with = "serde_rusoto_dynamodb::ttl")]
+            expires: SystemTime,
+        }
+
+        let expires = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        roundtrip(
+            &Test { expires },
+            &av_map(hashmap! { "expires".to_owned() => av_n("1700000000") }),
+        );
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Test {
+                expires: expires + Duration::from_millis(999),
+            }),
+            Ok(av_map(
+                hashmap! { "expires".to_owned() => av_n("1700000000") }
+            ))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Test {
+                expires: UNIX_EPOCH - Duration::from_secs(1),
+            }),
+            Err(Error::new("Time Before Unix Epoch Not Supported"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&av_map(
+                hashmap! { "expires".to_owned() => av_n("-1") }
+            )),
+            Err(Error::new("Number -1 Out Of Range For u64"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&av_map(
+                hashmap! { "expires".to_owned() => av_n("1700000000.5") }
+            )),
+            Err(Error::new("Integer Value Expected, Found 1700000000.5"))
+        );
+    }
+
+    // Nested Options
+
+    // Nested options collapse, as Some(None) and None are both serialized as
+    // null, while DoubleOption keeps them distinct.
+
+    #[test]
+    fn roundtrip_nested_options() {
+        let null = AttributeValue {
+            null: Some(true),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Some(None::<i32>)).unwrap(),
+            null
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Option<Option<i32>>>(&null).unwrap(),
+            None
+        );
+
+        roundtrip(&DoubleOption::<i32>(None), &null);
+        roundtrip(
+            &DoubleOption::<i32>(Some(None)),
+            &av_list(vec![null.clone()]),
+        );
+        roundtrip(&DoubleOption(Some(Some(1))), &av_list(vec![av_n(1)]));
+    }
+
+    // Char Keyed Maps
+
+    #[test]
+    fn roundtrip_char_keyed_map() {
+        roundtrip(
+            &hashmap! { 'a' => 1, '👍' => 2 },
+            &AttributeValue {
+                m: Some(hashmap! {
+                    "a".to_owned() => AttributeValue {
+                        n: Some("1".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                    "👍".to_owned() => AttributeValue {
+                        n: Some("2".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            },
+        );
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<HashMap<char, i32>>(&AttributeValue {
+                m: Some(hashmap! {
+                    "ab".to_owned() => AttributeValue {
+                        n: Some("1".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            }),
+            Err(Error::new("Single Character Key Expected, Found ab"))
+        );
+    }
+
+    // Enum Keyed Maps
+
+    #[test]
+    fn roundtrip_enum_keyed_map() {
+        #[derive(Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+        enum Color {
+            Red,
+            Green,
+        }
+
+        roundtrip(
+            &std::collections::BTreeMap::from([(Color::Red, 1), (Color::Green, 2)]),
+            &AttributeValue {
+                m: Some(hashmap! {
+                    "Red".to_owned() => av_n("1"),
+                    "Green".to_owned() => av_n("2"),
+                }),
+                ..AttributeValue::default()
+            },
+        );
+    }
+
+    // Enum Lists
+
+    // Each element of a list is deserialized as the element type requests, so
+    // enums within lists are deserialized as enums (rather than as maps).
+
+    #[test]
+    fn roundtrip_enum_list() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        enum Shape {
+            Empty,
+            Circle(u32),
+            Rectangle { width: u32, height: u32 },
+        }
+
+        let shapes = vec![
+            Shape::Circle(1),
+            Shape::Empty,
+            Shape::Rectangle {
+                width: 2,
+                height: 3,
+            },
+        ];
+        let expected = AttributeValue {
+            l: Some(vec![
+                av_map(hashmap! { "Circle".to_owned() => av_n("1") }),
+                av_map(hashmap! {
+                    "Empty".to_owned() => AttributeValue {
+                        null: Some(true),
+                        ..AttributeValue::default()
+                    },
+                }),
+                av_map(hashmap! {
+                    "Rectangle".to_owned() => av_map(hashmap! {
+                        "width".to_owned() => av_n("2"),
+                        "height".to_owned() => av_n("3"),
+                    }),
+                }),
+            ]),
+            ..AttributeValue::default()
+        };
+
+        roundtrip(&shapes, &expected);
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned::<Vec<Shape>>(expected),
+            Ok(shapes)
+        );
+    }
+
+    // Adjacently Tagged Enums
+
+    // Adjacently tagged enums are represented as maps with tag and content
+    // keys, and so round-trip as any other map. Serde serializes the tag as a
+    // unit variant, and so the tag uses the map form of unit variants.
+
+    #[test]
+    fn roundtrip_adjacently_tagged_enum() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        #[serde(tag = "t", content = "c")]
+        enum Test {
+            Unit,
+            Newtype(u8),
+            Struct { a: String },
+        }
+
+        roundtrip(
+            &Test::Unit,
+            &av_map(hashmap! {
+                "t".to_owned() => av_map(hashmap! { "Unit".to_owned() => av_null() }),
+            }),
+        );
+        roundtrip(
+            &Test::Newtype(1),
+            &av_map(hashmap! {
+                "t".to_owned() => av_map(hashmap! { "Newtype".to_owned() => av_null() }),
+                "c".to_owned() => av_n(1),
+            }),
+        );
+        roundtrip(
+            &Test::Struct {
+                a: "hello".to_owned(),
+            },
+            &av_map(hashmap! {
+                "t".to_owned() => av_map(hashmap! { "Struct".to_owned() => av_null() }),
+                "c".to_owned() => av_map(hashmap! { "a".to_owned() => av_s("hello") }),
+            }),
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&AttributeValue {
+                m: Some(hashmap! {
+                    "t".to_owned() => av_s("Newtype"),
+                    "c".to_owned() => AttributeValue {
+                        n: Some("2".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            }),
+            Ok(Test::Newtype(2))
+        );
+    }
+
+    // Integer Represented Enums
+
+    // Enums deriving Serialize_repr and Deserialize_repr are stored as number
+    // values (their integer representation), rather than by variant name.
+
+    #[test]
+    fn roundtrip_repr_enum() {
+        #[derive(Debug, Deserialize_repr, PartialEq, Serialize_repr)]
+        #[repr(u8)]
+        enum Test {
+            A = 1,
+            B = 2,
+            C = 255,
+        }
+
+        roundtrip(&Test::A, &av_n("1"));
+        roundtrip(&Test::B, &av_n("2"));
+        roundtrip(&Test::C, &av_n("255"));
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned::<Test>(av_n("255")),
+            Ok(Test::C)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&av_n("3")),
+            Err(Error::new("invalid value: 3, expected one of: 1, 2, 255"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&av_n("256")),
+            Err(Error::new("Number 256 Out Of Range For u8"))
+        );
+    }
+
+    // Tagged Enums
+
+    // Where an enum tag is configured, enums at any level store the variant
+    // name under the tag alongside the fields of the variant (as written by
+    // other AWS SDKs for polymorphic attributes), and are read back with the
+    // same tag configured, without annotating the enum.
+
+    #[test]
+    fn roundtrip_enum_tag() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Circle {
+            radius: u8,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        enum Shape {
+            Circle(Circle),
+            Point,
+            Rect { w: u8, h: u8 },
+            Pair(u8, u8),
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Drawing {
+            name: String,
+            shapes: Vec<Shape>,
+        }
+
+        let ser_config = SerializerConfig {
+            enum_tag: Some("type".to_owned()),
+            ..SerializerConfig::default()
+        };
+        let de_config = DeserializerConfig {
+            enum_tag: Some("type".to_owned()),
+            ..DeserializerConfig::default()
+        };
+
+        let drawing = Drawing {
+            name: "d".to_owned(),
+            shapes: vec![
+                Shape::Circle(Circle { radius: 1 }),
+                Shape::Point,
+                Shape::Rect { w: 2, h: 3 },
+            ],
+        };
+        let expected = av_map(hashmap! {
+            "name".to_owned() => av_s("d"),
+            "shapes".to_owned() => AttributeValue {
+                l: Some(vec![
+                    av_map(hashmap! {
+                        "type".to_owned() => av_s("Circle"),
+                        "radius".to_owned() => av_n("1"),
+                    }),
+                    av_map(hashmap! { "type".to_owned() => av_s("Point") }),
+                    av_map(hashmap! {
+                        "type".to_owned() => av_s("Rect"),
+                        "w".to_owned() => av_n("2"),
+                        "h".to_owned() => av_n("3"),
+                    }),
+                ]),
+                ..AttributeValue::default()
+            },
+        });
+
+        roundtrip_with_config(&drawing, &expected, &ser_config, &de_config);
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned_with_config::<Drawing>(
+                expected, &de_config
+            ),
+            Ok(drawing)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<Shape>(
+                &av_map(hashmap! { "Point".to_owned() => AttributeValue {
+                    null: Some(true),
+                    ..AttributeValue::default()
+                }}),
+                &de_config
+            ),
+            Ok(Shape::Point)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                vec![Shape::Pair(1, 2)],
+                &ser_config
+            ),
+            Err(Error::new(
+                "at [0]: Tagged Variant Pair Must Not Be A Tuple Variant"
+            ))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<Shape>(
+                &av_map(hashmap! { "type".to_owned() => av_s("Pair") }),
+                &de_config
+            ),
+            Err(Error::new(
+                "Tagged Variant Pair Must Not Be A Tuple Variant"
+            ))
+        );
+
+        // Unit variant map keys are written as the variant name, and are not
+        // tagged.
+
+        #[derive(Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+        enum Color {
+            Red,
+        }
+
+        roundtrip_with_config(
+            &hashmap! { Color::Red => 1 },
+            &av_map(hashmap! { "Red".to_owned() => av_n("1") }),
+            &ser_config,
+            &de_config,
+        );
+    }
+
+    // Item Enums
+
+    // Enums serialized as entire items are read back from the item as they are
+    // from a map value, whether directly or within an option.
+
+    #[test]
+    fn roundtrip_item_enum() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        enum Shape {
+            Circle { r: u8 },
+            Point,
+        }
+
+        for shape in [Shape::Circle { r: 1 }, Shape::Point] {
+            let item = serde_rusoto_dynamodb::to_item(&shape).unwrap();
+
+            assert_eq!(serde_rusoto_dynamodb::from_item::<Shape>(&item), Ok(shape));
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_item::<Option<Shape>>(&hashmap! {
+                "Circle".to_owned() => av_map(hashmap! { "r".to_owned() => av_n("1") }),
+            }),
+            Ok(Some(Shape::Circle { r: 1 }))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_item::<Shape>(&HashMap::new()),
+            Err(Error::new(
+                "Map Value With A Single Key Expected, Found 0 Keys"
+            ))
+        );
+    }
+
+    // Tagged enums are read back from items as from map values, and newtype
+    // variants whose value is not a map (such as those of Result) fall back to
+    // the singly-keyed map form where a tag is configured.
+
+    #[test]
+    fn roundtrip_item_enum_tag() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        enum Shape {
+            Circle { r: u8 },
+            Point,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            result: Result<String, String>,
+        }
+
+        let ser_config = SerializerConfig {
+            enum_tag: Some("type".to_owned()),
+            ..SerializerConfig::default()
+        };
+        let de_config = DeserializerConfig {
+            enum_tag: Some("type".to_owned()),
+            ..DeserializerConfig::default()
+        };
+
+        for (shape, expected) in [
+            (
+                Shape::Circle { r: 1 },
+                hashmap! {
+                    "type".to_owned() => av_s("Circle"),
+                    "r".to_owned() => av_n("1"),
+                },
+            ),
+            (
+                Shape::Point,
+                hashmap! { "type".to_owned() => av_s("Point") },
+            ),
+        ] {
+            let item = serde_rusoto_dynamodb::to_item_with_config(&shape, &ser_config).unwrap();
+
+            assert_eq!(item, expected);
+            assert_eq!(
+                serde_rusoto_dynamodb::from_item_with_config::<Shape>(&item, &de_config),
+                Ok(shape)
+            );
+        }
+
+        roundtrip_with_config(
+            &Test {
+                result: Ok("x".to_owned()),
+            },
+            &av_map(hashmap! {
+                "result".to_owned() => av_map(hashmap! { "Ok".to_owned() => av_s("x") }),
+            }),
+            &ser_config,
+            &de_config,
+        );
+    }
+
+    // Enums serialized as entire items with an item enum tag are read back
+    // where the same item enum tag is configured, while nested enums use the
+    // singly-keyed map form (or the enum tag, where configured) throughout.
+
+    #[test]
+    fn roundtrip_configured_item_enum_tag() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        enum Color {
+            Red,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        enum Shape {
+            Circle { r: u8, color: Color },
+            Point,
+        }
+
+        let ser_config = SerializerConfig {
+            item_enum_tag: Some("__type".to_owned()),
+            ..SerializerConfig::default()
+        };
+        let de_config = DeserializerConfig {
+            item_enum_tag: Some("__type".to_owned()),
+            ..DeserializerConfig::default()
+        };
+
+        let circle = Shape::Circle {
+            r: 1,
+            color: Color::Red,
+        };
+        let item = serde_rusoto_dynamodb::to_item_with_config(&circle, &ser_config).unwrap();
+
+        assert_eq!(
+            item,
+            hashmap! {
+                "__type".to_owned() => av_s("Circle"),
+                "r".to_owned() => av_n("1"),
+                "color".to_owned() => av_map(hashmap! {
+                    "Red".to_owned() => AttributeValue {
+                        null: Some(true),
+                        ..AttributeValue::default()
+                    },
+                }),
+            }
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_item_with_config::<Shape>(&item, &de_config),
+            Ok(circle)
+        );
+
+        roundtrip_with_config(
+            &Shape::Point,
+            &av_map(hashmap! { "__type".to_owned() => av_s("Point") }),
+            &ser_config,
+            &de_config,
+        );
+        roundtrip_with_config(
+            &vec![Shape::Point],
+            &av_list(vec![av_map(hashmap! {
+                "Point".to_owned() => AttributeValue {
+                    null: Some(true),
+                    ..AttributeValue::default()
+                },
+            })]),
+            &ser_config,
+            &de_config,
+        );
+    }
+
+    #[test]
+    fn serialize_unit() {
+        roundtrip(
+            &(),
+            &AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            },
+        )
+    }
+
+    // #[test]
+    // fn serialize_unit_variant() {
+    //     #[derive(Serialize)]
+    //     enum Test {
+    //         Unit,
+    //     }
+
+    //     let test: Test = Test::Unit;
+
+    //     assert_eq!(
+    //         serde_rusoto_dynamodb::to_attribute_value(test),
+    //         Ok(av_s("Unit"))
+    //     );
+    // }
+}
+
+// Serialize
+
+#[cfg(test)]
+mod serialize {
+
+    use super::*;
+    use maplit::hashmap;
+
+    // Numeric Values
+
+    // Numeric formatting must be identical for integers regardless of the
+    // number formatting backend in use (with or without the default
+    // "fast-number-format" feature), and agree for common float values.
+
+    #[test]
+    fn serialize_numeric_format() {
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(i64::MIN),
+            Ok(AttributeValue {
+                n: Some("-9223372036854775808".to_owned()),
+                ..AttributeValue::default()
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(u64::MAX),
+            Ok(AttributeValue {
+                n: Some("18446744073709551615".to_owned()),
+                ..AttributeValue::default()
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(-1.5f64),
+            Ok(AttributeValue {
+                n: Some("-1.5".to_owned()),
+                ..AttributeValue::default()
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(2f64),
+            Ok(AttributeValue {
+                n: Some("2.0".to_owned()),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
+    // f32 values are formatted as f32 (the shortest form which parses back to
+    // the same f32), rather than gaining spurious precision from widening to
+    // f64, in either number formatting backend.
+
+    #[test]
+    fn serialize_f32_format() {
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(0.1f32),
+            Ok(av_n("0.1"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(-1.1f32),
+            Ok(av_n("-1.1"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(f32::MAX),
+            Ok(av_n("3.4028235e38"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(0.1f32 as f64),
+            Ok(av_n("0.10000000149011612"))
+        );
+    }
+
+    // Borrowed Values
+
+    #[test]
+    fn serialize_borrowed() {
+        #[derive(Serialize)]
+        struct Test {
+            a: String,
+        }
+
+        let test = Test {
+            a: "hello".to_owned(),
+        };
+        let expected = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    s: Some("hello".to_owned()),
+                    ..AttributeValue::default()
+                }
+            }),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_ref(&test),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(&test),
+            Ok(expected)
+        );
+        assert_eq!(test.a, "hello");
+    }
+
+    // Map Values
+
+    #[test]
+    fn serialize_large_map() {
+        let map: HashMap<String, usize> = (0..1000).map(|i| (format!("key{}", i), i)).collect();
+
+        let expected: HashMap<String, AttributeValue> = map
+            .iter()
+            .map(|(key, value)| {
+                (
+                    key.to_owned(),
+                    AttributeValue {
+                        n: Some(value.to_string()),
+                        ..AttributeValue::default()
+                    },
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(&map),
+            Ok(AttributeValue {
+                m: Some(expected),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_map_non_string_key() {
+        #[derive(Debug, Eq, Hash, PartialEq, Serialize)]
+        struct Key {
+            id: i32,
+        }
+
+        let mut map = HashMap::new();
+        map.insert(Key { id: 1 }, 1);
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(&map),
+            Err(Error::new("Map Key Must Serialize To String, Got Map"))
+        );
+    }
+
+    // Keys are serialized directly as strings, so a struct key with a single
+    // null field is rejected as any other struct, rather than mistaken for a
+    // unit variant.
+
+    #[test]
+    fn serialize_map_single_null_field_key() {
+        #[derive(Debug, Eq, Hash, PartialEq, Serialize)]
+        struct Key {
+            a: Option<u8>,
+        }
+
+        let mut map = HashMap::new();
+        map.insert(Key { a: None }, 1);
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(&map),
+            Err(Error::new("Map Key Must Serialize To String, Got Map"))
+        );
+    }
+
+    #[test]
+    fn serialize_map_non_string_key_path() {
+        #[derive(Debug, Eq, Hash, PartialEq, Serialize)]
+        struct Key {
+            id: i32,
+        }
+
+        #[derive(Serialize)]
+        struct Test {
+            config: HashMap<Key, i32>,
+            nested: Vec<HashMap<i32, i32>>,
+        }
+
+        let mut config = HashMap::new();
+        config.insert(Key { id: 1 }, 1);
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Test {
+                config,
+                nested: Vec::new(),
+            }),
+            Err(Error::new(
+                "at config: Map Key Must Serialize To String, Got Map"
+            ))
+        );
+
+        let mut nested = HashMap::new();
+        nested.insert(1, 1);
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Test {
+                config: HashMap::new(),
+                nested: vec![HashMap::new(), nested],
+            }),
+            Err(Error::new(
+                "at nested[1]: Map Key Must Serialize To String, Got Number"
+            ))
+        );
+    }
+
+    // Duplicate map keys replace earlier values by default, and are rejected
+    // where configured.
+
+    #[test]
+    fn serialize_map_duplicate_keys() {
+        use serde::ser::{SerializeMap, Serializer};
+
+        struct Duplicates;
+
+        impl Serialize for Duplicates {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let mut map = serializer.serialize_map(Some(3))?;
+                map.serialize_entry("a", &1)?;
+                map.serialize_entry("b", &2)?;
+                map.serialize_key("a")?;
+                map.serialize_value(&3)?;
+                map.end()
+            }
+        }
+
+        #[derive(Serialize)]
+        struct Test {
+            values: Duplicates,
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Duplicates),
+            Ok(AttributeValue {
+                m: Some(hashmap! {
+                    "a".to_owned() => av_n("3"),
+                    "b".to_owned() => av_n("2"),
+                }),
+                ..AttributeValue::default()
+            })
+        );
+
+        let config = SerializerConfig {
+            reject_duplicate_keys: true,
+            ..SerializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(Duplicates, &config),
+            Err(Error::new("Duplicate Map Key a Not Allowed"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                Test { values: Duplicates },
+                &config
+            ),
+            Err(Error::new("at values: Duplicate Map Key a Not Allowed"))
+        );
+
+        // Keys are rejected even where the earlier value was omitted.
+
+        struct OmittedDuplicates;
+
+        impl Serialize for OmittedDuplicates {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("a", &None::<u8>)?;
+                map.serialize_entry("a", &Some(1))?;
+                map.end()
+            }
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                OmittedDuplicates,
+                &SerializerConfig {
+                    omit_none: true,
+                    ..config.clone()
+                }
+            ),
+            Err(Error::new("Duplicate Map Key a Not Allowed"))
+        );
+    }
+
+    // Malformed Serialize implementations which serialize a map value without
+    // first serializing its key are rejected, rather than panicking or
+    // inserting the value under a previous key.
+
+    #[test]
+    fn serialize_map_value_before_key() {
+        struct Malformed {
+            keys: usize,
+        }
+
+        impl Serialize for Malformed {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                use serde::ser::SerializeMap;
+
+                let mut map = serializer.serialize_map(None)?;
+
+                for _ in 0..self.keys {
+                    map.serialize_key("a")?;
+                }
+
+                map.serialize_value(&1)?;
+                map.serialize_value(&2)?;
+                map.end()
+            }
+        }
+
+        let error = Err(Error::new("Key Must Be Set and Value Must Be Serializable"));
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Malformed { keys: 0 }),
+            error
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Malformed { keys: 1 }),
+            error
+        );
+    }
+
+    // Serializing map entries gives the same results as serializing keys and
+    // values separately, including for unit variant keys, omitted values and
+    // invalid keys.
+
+    #[test]
+    fn serialize_map_entries() {
+        #[derive(Serialize)]
+        enum Color {
+            Red,
+        }
+
+        struct Entries<K, V> {
+            entries: Vec<(K, V)>,
+            split: bool,
+        }
+
+        impl<K: Serialize, V: Serialize> Serialize for Entries<K, V> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                use serde::ser::SerializeMap;
+
+                let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+
+                for (key, value) in &self.entries {
+                    if self.split {
+                        map.serialize_key(key)?;
+                        map.serialize_value(value)?;
+                    } else {
+                        map.serialize_entry(key, value)?;
+                    }
+                }
+
+                map.end()
+            }
+        }
+
+        let config = SerializerConfig {
+            omit_none: true,
+            ..SerializerConfig::default()
+        };
+        let both = |entries: Vec<(Color, Option<i32>)>| {
+            [true, false].map(|split| {
+                serde_rusoto_dynamodb::to_attribute_value_with_config(
+                    Entries {
+                        entries: entries.iter().map(|(k, v)| (k, *v)).collect(),
+                        split,
+                    },
+                    &config,
+                )
+            })
+        };
+
+        let [split, entry] = both(vec![(Color::Red, Some(1))]);
+
+        assert_eq!(split, entry);
+        assert_eq!(
+            entry,
+            Ok(AttributeValue {
+                m: Some(hashmap! {
+                    "Red".to_owned() => AttributeValue {
+                        n: Some("1".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            })
+        );
+
+        let [split, entry] = both(vec![(Color::Red, None)]);
+
+        assert_eq!(split, entry);
+        assert_eq!(
+            entry,
+            Ok(AttributeValue {
+                m: Some(HashMap::new()),
+                ..AttributeValue::default()
+            })
+        );
+
+        let invalid = |split| {
+            serde_rusoto_dynamodb::to_attribute_value(Entries {
+                entries: vec![(1, 1)],
+                split,
+            })
+        };
+
+        assert_eq!(invalid(true), invalid(false));
+        assert_eq!(
+            invalid(false),
+            Err(Error::new("Map Key Must Serialize To String, Got Number"))
+        );
+    }
+
+    // Set Values
+
+    #[test]
+    fn serialize_invalid_sets() {
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(StringSet(Vec::<String>::new())),
+            Err(Error::new("String Set Must Not Be Empty"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(StringSet(vec![1])),
+            Err(Error::new(
+                "String Set Elements Must Be Of A Matching Kind, Got Number"
+            ))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(NumberSet(1)),
+            Err(Error::new("Number Set Must Serialize From Seq, Got Number"))
+        );
+    }
+
+    // Display Values
+
+    #[test]
+    fn serialize_collect_str() {
+        struct Version(u8, u8);
+
+        impl Serialize for Version {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.collect_str(&format_args!("v{}.{}", self.0, self.1))
+            }
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Version(1, 2)),
+            Ok(AttributeValue {
+                s: Some("v1.2".to_owned()),
+                ..AttributeValue::default()
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                Version(10, 20),
+                &SerializerConfig {
+                    max_value_bytes: Some(4),
+                    ..SerializerConfig::default()
+                }
+            ),
+            Err(Error::new("Value Of 6 Bytes Exceeds Maximum Of 4 Bytes"))
+        );
+    }
+
+    // Display fields are formatted once, when serialized, and not when probed
+    // (as each struct field is, to recognise markers).
+
+    #[test]
+    fn serialize_collect_str_fields() {
+        use std::{
+            cell::Cell,
+            fmt::{Display, Formatter, Result as FmtResult},
+        };
+
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl<'a> Display for Counted<'a> {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                self.0.set(self.0.get() + 1);
+                f.write_str("x")
+            }
+        }
+
+        impl<'a> Serialize for Counted<'a> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.collect_str(self)
+            }
+        }
+
+        #[derive(Serialize)]
+        struct Test<'a> {
+            id: Counted<'a>,
+        }
+
+        let count = Cell::new(0);
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(Test {
+                id: Counted(&count)
+            }),
+            Ok(av_map(hashmap! { "id".to_owned() => av_s("x") }))
+        );
+        assert_eq!(count.get(), 1);
+    }
+
+    // Byte Values
+
+    #[test]
+    fn serialize_owned_bytes() {
+        let bytes = vec![0u8; 1024];
+        let ptr = bytes.as_ptr();
+        let owned = serde_rusoto_dynamodb::OwnedBytes::from(bytes);
+
+        match serde_rusoto_dynamodb::to_attribute_value(&owned) {
+            Ok(AttributeValue { b: Some(b), .. }) => {
+                assert_eq!(&b[..], &[0u8; 1024][..]);
+                assert!(std::ptr::eq(b.as_ptr(), ptr));
+            }
+            value => panic!("Binary Value Expected, Found {:?}", value),
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                &owned,
+                &SerializerConfig {
+                    max_value_bytes: Some(512),
+                    ..SerializerConfig::default()
+                }
+            ),
+            Err(Error::new(
+                "Value Of 1024 Bytes Exceeds Maximum Of 512 Bytes"
+            ))
+        );
+        assert_eq!(
+            serde_json::to_string(&serde_rusoto_dynamodb::OwnedBytes::from(vec![1u8, 2])).unwrap(),
+            "[1,2]"
+        );
+    }
+
+    // Sequences of u8 are serialized as binary where configured (detected by
+    // how elements serialize, so other small numbers are unaffected), and
+    // binary values deserialize as sequences of u8.
+
+    #[test]
+    fn serialize_vec_u8_as_binary() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            bytes: Vec<u8>,
+            empty: Vec<u8>,
+            numbers: Vec<i32>,
+        }
+
+        roundtrip_with_config(
+            &Test {
+                bytes: vec![0, 1, 255],
+                empty: vec![],
+                numbers: vec![1, 2],
+            },
+            &AttributeValue {
+                m: Some(hashmap! {
+                    "bytes".to_owned() => AttributeValue {
+                        b: Some(vec![0, 1, 255].into()),
+                        ..AttributeValue::default()
+                    },
+                    "empty".to_owned() => AttributeValue {
+                        l: Some(vec![]),
+                        ..AttributeValue::default()
+                    },
+                    "numbers".to_owned() => AttributeValue {
+                        l: Some(vec![av_n("1"), av_n("2")]),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            },
+            &SerializerConfig {
+                vec_u8_as_binary: true,
+                ..SerializerConfig::default()
+            },
+            &DeserializerConfig::default(),
+        );
+    }
+
+    #[test]
+    fn serialize_vec_u8_as_binary_elements() {
+        #[derive(Serialize)]
+        #[serde(untagged)]
+        enum Element {
+            Byte(u8),
+            Text(&'static str),
+        }
+
+        let config = SerializerConfig {
+            vec_u8_as_binary: true,
+            ..SerializerConfig::default()
+        };
+
+        let (a, b) = (1u8, 2u8);
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(vec![&&a, &&b], &config),
+            Ok(AttributeValue {
+                b: Some(vec![1, 2].into()),
+                ..AttributeValue::default()
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                vec![Element::Byte(1), Element::Byte(2), Element::Text("a")],
+                &config
+            ),
+            Ok(AttributeValue {
+                l: Some(vec![
+                    AttributeValue {
+                        n: Some("1".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                    AttributeValue {
+                        n: Some("2".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                    AttributeValue {
+                        s: Some("a".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                ]),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
+    // Omitted Values
+
+    #[test]
+    fn serialize_map_omit_none() {
+        let map: HashMap<String, Option<i32>> = hashmap! {
+            "a".to_owned() => Some(1),
+            "b".to_owned() => None,
+        };
+
+        let a = AttributeValue {
+            n: Some("1".to_owned()),
+            ..AttributeValue::default()
+        };
+        let b = AttributeValue {
+            null: Some(true),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(&map),
+            Ok(AttributeValue {
+                m: Some(hashmap! {
+                    "a".to_owned() => a.clone(),
+                    "b".to_owned() => b,
+                }),
+                ..AttributeValue::default()
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                &map,
+                &SerializerConfig {
+                    omit_none: true,
+                    ..SerializerConfig::default()
+                }
+            ),
+            Ok(AttributeValue {
+                m: Some(hashmap! {
+                    "a".to_owned() => a,
+                }),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_struct_omit_none() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            a: Option<i32>,
+            b: Option<i32>,
+        }
+
+        roundtrip_with_config(
+            &Test {
+                a: Some(1),
+                b: None,
+            },
+            &AttributeValue {
+                m: Some(hashmap! {
+                    "a".to_owned() => AttributeValue {
+                        n: Some("1".to_owned()),
+                        ..AttributeValue::default()
+                    }
+                }),
+                ..AttributeValue::default()
+            },
+            &SerializerConfig {
+                omit_none: true,
+                ..SerializerConfig::default()
+            },
+            &DeserializerConfig::default(),
+        );
+    }
+
+    // Attribute Hooks
+
+    // The attribute hook observes each stored field of a top level struct (but
+    // not omitted fields, or the fields of nested structs).
+
+    #[test]
+    fn serialize_on_attribute() {
+        use std::cell::RefCell;
+
+        thread_local! {
+            static NAMES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn record(name: &str, _value: &AttributeValue) {
+            NAMES.with(|names| names.borrow_mut().push(name.to_owned()));
+        }
+
+        #[derive(Serialize)]
+        struct Inner {
+            c: i32,
+        }
+
+        #[derive(Serialize)]
+        struct Test {
+            a: Option<i32>,
+            b: Inner,
+            d: Option<i32>,
+        }
+
+        serde_rusoto_dynamodb::to_item_with_config(
+            Test {
+                a: Some(1),
+                b: Inner { c: 2 },
+                d: None,
+            },
+            &SerializerConfig {
+                omit_none: true,
+                on_attribute: Some(record),
+                ..SerializerConfig::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            NAMES.with(|names| names.take()),
+            vec!["a".to_owned(), "b".to_owned()]
+        );
+    }
+
+    // Items
+
+    // Maps serialize to items as structs do, with each entry becoming an
+    // attribute (rather than the map being nested within the item).
+
+    #[test]
+    fn serialize_map_items() {
+        #[derive(Serialize)]
+        struct Test {
+            a: i32,
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_item(&hashmap! {
+                "a".to_owned() => "x".to_owned(),
+                "b".to_owned() => "y".to_owned(),
+            }),
+            Ok(hashmap! {
+                "a".to_owned() => av_s("x"),
+                "b".to_owned() => av_s("y"),
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_item(&hashmap! {
+                "first".to_owned() => Test { a: 1 },
+            }),
+            Ok(hashmap! {
+                "first".to_owned() => AttributeValue {
+                    m: Some(hashmap! {
+                        "a".to_owned() => AttributeValue {
+                            n: Some("1".to_owned()),
+                            ..AttributeValue::default()
+                        },
+                    }),
+                    ..AttributeValue::default()
+                },
+            })
+        );
+    }
+
+    // Items are serialized lazily from an iterator of values, so iterators
+    // need not be finite, and errors are reported for each item.
+
+    #[test]
+    fn serialize_items_iter() {
+        #[derive(Serialize)]
+        struct Test {
+            id: usize,
+        }
+
+        let items = serde_rusoto_dynamodb::to_items_iter((0..).map(|id| Test { id }))
+            .take(100)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(items.len(), 100);
+        assert_eq!(
+            items[99],
+            hashmap! {
+                "id".to_owned() => AttributeValue {
+                    n: Some("99".to_owned()),
+                    ..AttributeValue::default()
+                },
+            }
+        );
+
+        let config = SerializerConfig {
+            validate_names: true,
+            ..SerializerConfig::default()
+        };
+        let mut items = serde_rusoto_dynamodb::to_items_iter_with_config(
+            vec![
+                hashmap! { "a" => 1 },
+                hashmap! { "b" => 2 },
+                hashmap! { "c" => 3 },
+            ],
+            &config,
+        )
+        .map(|item| item.is_ok());
+
+        assert_eq!(items.next(), Some(true));
+        assert_eq!(items.size_hint(), (2, Some(2)));
+        assert!(serde_rusoto_dynamodb::to_items_iter(vec![1])
+            .next()
+            .unwrap()
+            .is_err());
+    }
+
+    // Expression Values
+
+    #[test]
+    fn serialize_expression_values() {
+        #[derive(Serialize)]
+        struct Test {
+            a: i32,
+            b: String,
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_expression_values(
+                Test {
+                    a: 1,
+                    b: "hello".to_owned()
+                },
+                ":"
+            ),
+            Ok(hashmap! {
+                ":a".to_owned() => AttributeValue {
+                    n: Some("1".to_owned()),
+                    ..AttributeValue::default()
+                },
+                ":b".to_owned() => AttributeValue {
+                    s: Some("hello".to_owned()),
+                    ..AttributeValue::default()
+                },
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_expression_values(1, ":"),
+            Err(Error::new("Map Value Expected, Found Number"))
+        );
+    }
+
+    // Tagged Item Enums
+
+    // Enums serialized as entire items store the variant name under the
+    // configured tag, and can be deserialized using the internally tagged
+    // representation with the same tag. Nested enums are unaffected.
+
+    #[test]
+    fn serialize_item_enum_tag() {
+        #[derive(Serialize)]
+        struct Circle {
+            radius: u8,
+        }
+
+        #[derive(Serialize)]
+        enum Shape {
+            Circle(Circle),
+            Point,
+            Rect { w: u8, h: u8 },
+            Pair(u8, u8),
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(tag = "__type")]
+        enum TaggedShape {
+            Circle { radius: u8 },
+            Point,
+            Rect { w: u8, h: u8 },
+        }
+
+        let config = SerializerConfig {
+            item_enum_tag: Some("__type".to_owned()),
+            ..SerializerConfig::default()
+        };
+        let to_item = |value: &Shape| serde_rusoto_dynamodb::to_item_with_config(value, &config);
+
+        let rect = to_item(&Shape::Rect { w: 2, h: 3 }).unwrap();
+
+        assert_eq!(
+            rect,
+            hashmap! {
+                "__type".to_owned() => av_s("Rect"),
+                "w".to_owned() => av_n("2"),
+                "h".to_owned() => av_n("3"),
+            }
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_item::<TaggedShape>(&rect),
+            Ok(TaggedShape::Rect { w: 2, h: 3 })
+        );
+        assert_eq!(
+            to_item(&Shape::Circle(Circle { radius: 1 })),
+            Ok(hashmap! {
+                "__type".to_owned() => av_s("Circle"),
+                "radius".to_owned() => av_n("1"),
+            })
+        );
+        assert_eq!(
+            to_item(&Shape::Point),
+            Ok(hashmap! { "__type".to_owned() => av_s("Point") })
+        );
+        assert_eq!(
+            to_item(&Shape::Pair(1, 2)),
+            Err(Error::new(
+                "Tagged Variant Pair Must Not Be A Tuple Variant"
+            ))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(vec![Shape::Point], &config),
+            Ok(AttributeValue {
+                l: Some(vec![AttributeValue {
+                    m: Some(hashmap! {
+                        "Point".to_owned() => AttributeValue {
+                            null: Some(true),
+                            ..AttributeValue::default()
+                        },
+                    }),
+                    ..AttributeValue::default()
+                }]),
+                ..AttributeValue::default()
+            })
+        );
+
+        // The value of a newtype variant is not itself the item enum, so a
+        // nested enum is not tagged with the item enum tag (but is tagged with
+        // the enum tag, where configured).
+
+        #[derive(Serialize)]
+        enum Inner {
+            X { a: u8 },
+        }
+
+        #[derive(Serialize)]
+        enum Outer {
+            W(Inner),
+        }
+
+        let outer = Outer::W(Inner::X { a: 1 });
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_item_with_config(&outer, &config),
+            Ok(hashmap! {
+                "__type".to_owned() => av_s("W"),
+                "X".to_owned() => AttributeValue {
+                    m: Some(hashmap! { "a".to_owned() => av_n("1") }),
+                    ..AttributeValue::default()
+                },
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_item_with_config(
+                &outer,
+                &SerializerConfig {
+                    enum_tag: Some("type".to_owned()),
+                    ..config.clone()
+                }
+            ),
+            Ok(hashmap! {
+                "__type".to_owned() => av_s("W"),
+                "type".to_owned() => av_s("X"),
+                "a".to_owned() => av_n("1"),
+            })
+        );
+    }
+
+    // Coerced Values
+
+    #[test]
+    fn serialize_newtype_coercion() {
+        #[derive(Serialize)]
+        struct Id(String);
+
+        #[derive(Serialize)]
+        struct Name(String);
+
+        #[derive(Serialize)]
+        struct Test {
+            id: Id,
+            name: Name,
+        }
+
+        fn to_binary(value: AttributeValue) -> Result<AttributeValue, Error> {
+            match value {
+                AttributeValue { s: Some(s), .. } => Ok(AttributeValue {
+                    b: Some(s.into_bytes().into()),
+                    ..AttributeValue::default()
+                }),
+                _ => Err(Error::new("String Expected")),
+            }
+        }
+
+        let config = SerializerConfig {
+            coercions: hashmap! { "Id" => to_binary as Coercion },
+            ..SerializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                Test {
+                    id: Id("abc".to_owned()),
+                    name: Name("def".to_owned()),
+                },
+                &config
+            ),
+            Ok(AttributeValue {
+                m: Some(hashmap! {
+                    "id".to_owned() => AttributeValue {
+                        b: Some(b"abc".to_vec().into()),
+                        ..AttributeValue::default()
+                    },
+                    "name".to_owned() => AttributeValue {
+                        s: Some("def".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
+    // Value Sizes
+
+    #[test]
+    fn serialize_max_value_bytes() {
+        #[derive(Serialize)]
+        struct Test {
+            tags: Vec<String>,
+        }
+
+        let config = SerializerConfig {
+            max_value_bytes: Some(5),
+            ..SerializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config("hello", &config),
+            Ok(AttributeValue {
+                s: Some("hello".to_owned()),
+                ..AttributeValue::default()
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config("hello!", &config),
+            Err(Error::new("Value Of 6 Bytes Exceeds Maximum Of 5 Bytes"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                Test {
+                    tags: vec!["a".to_owned(), "hello!".to_owned()]
+                },
+                &config
+            ),
+            Err(Error::new(
+                "at tags[1]: Value Of 6 Bytes Exceeds Maximum Of 5 Bytes"
+            ))
+        );
+
+        // Sequences of u8 serialized as binary are limited as other binary
+        // values are.
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                vec![1u8, 2, 3, 4, 5, 6],
+                &SerializerConfig {
+                    vec_u8_as_binary: true,
+                    ..config.clone()
+                }
+            ),
+            Err(Error::new("Value Of 6 Bytes Exceeds Maximum Of 5 Bytes"))
+        );
+    }
+
+    // Attribute names and nesting are validated against the DynamoDB limits
+    // (255 bytes and 32 levels respectively) where configured, not counting
+    // the top level value as a level of nesting.
+
+    #[test]
+    fn serialize_validate_names() {
+        #[derive(Serialize)]
+        struct Test {
+            #[serde(
+                rename = "nnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnn"
+            )]
+            long: i32,
+        }
+
+        let config = SerializerConfig {
+            validate_names: true,
+            ..SerializerConfig::default()
+        };
+        let nested = |depth: usize| {
+            (0..depth).fold(serde_json::Value::Null, |value, _| {
+                serde_json::Value::Array(vec![value])
+            })
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(Test { long: 1 }, &config),
+            Err(Error::new(
+                "Attribute Name Of 256 Bytes Exceeds Maximum Of 255 Bytes"
+            ))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(
+                hashmap! { "a" => hashmap! { "b".repeat(256) => 1 } },
+                &config
+            ),
+            Err(Error::new(
+                "at a: Attribute Name Of 256 Bytes Exceeds Maximum Of 255 Bytes"
+            ))
+        );
+        assert!(serde_rusoto_dynamodb::to_attribute_value_with_config(nested(33), &config).is_ok());
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(nested(34), &config)
+                .map_err(|e| e.message.ends_with("Nesting Exceeds Maximum Depth Of 32")),
+            Err(true)
+        );
+        assert!(serde_rusoto_dynamodb::to_attribute_value(Test { long: 1 }).is_ok());
+        assert!(serde_rusoto_dynamodb::to_attribute_value(nested(34)).is_ok());
+    }
+
+    // Float Values
+
+    #[test]
+    fn serialize_trim_float_integers() {
+        let config = SerializerConfig {
+            trim_float_integers: true,
+            ..SerializerConfig::default()
+        };
+        let n = |n: &str| {
+            Ok(AttributeValue {
+                n: Some(n.to_owned()),
+                ..AttributeValue::default()
+            })
+        };
+
+        assert_eq!(serde_rusoto_dynamodb::to_attribute_value(2f64), n("2.0"));
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(2f64, &config),
+            n("2")
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(100f32, &config),
+            n("100")
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value_with_config(2.5f64, &config),
+            n("2.5")
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<f64>(&n("2").unwrap()),
+            Ok(2f64)
+        );
+    }
+}
+
+// Deserialize
+
+#[cfg(test)]
+mod deserialize {
+
+    use super::*;
+    use maplit::hashmap;
+    use serde_json::json;
+    use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+    // Char Values
+
+    #[test]
+    fn deserialize_character_length() {
+        for s in &["👍x", "e\u{301}", ""] {
+            let value = AttributeValue {
+                s: Some((*s).to_owned()),
+                ..AttributeValue::default()
+            };
+
+            assert_eq!(
+                serde_rusoto_dynamodb::from_attribute_value::<char>(&value),
+                Err(Error::new("Single Character String Expected"))
+            );
+        }
+    }
+
+    // Boolean Values
+
+    #[test]
+    fn deserialize_bool_strict() {
+        let value = AttributeValue {
+            n: Some("1".to_owned()),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<bool>(&value),
+            Err(Error::new("Boolean Value Expected, Found Number"))
+        );
+    }
+
+    #[test]
+    fn deserialize_bool_coerced() {
+        let config = DeserializerConfig {
+            coerce_bool: true,
+            ..DeserializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<bool>(&av_n("0"), &config),
+            Ok(false)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<bool>(&av_n("1"), &config),
+            Ok(true)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<bool>(&av_n("2"), &config),
+            Err(Error::new("Number 0 Or 1 Expected, Found 2"))
+        );
+    }
+
+    // Newtypes deserialize their inner value as that type, and so newtype flags
+    // are coerced from numbers as any other boolean would be.
+
+    #[test]
+    fn deserialize_bool_coerced_newtype() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Flag(bool);
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            flag: Flag,
+        }
+
+        let config = DeserializerConfig {
+            coerce_bool: true,
+            ..DeserializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<Flag>(&av_n("1"), &config),
+            Ok(Flag(true))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<Test>(
+                &AttributeValue {
+                    m: Some(hashmap! { "flag".to_owned() => av_n("0") }),
+                    ..AttributeValue::default()
+                },
+                &config
+            ),
+            Ok(Test { flag: Flag(false) })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Flag>(&av_n("1")),
+            Err(Error::new("Boolean Value Expected, Found Number"))
+        );
+    }
+
+    // Scalar types are coerced between strings and numbers only where
+    // configured, with numbers yielding strings as written. Borrowed strings
+    // are the exception, and are read from numbers regardless (see
+    // deserialize_str).
+
+    #[test]
+    fn deserialize_scalar_types_coerced() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            id: String,
+            count: i64,
+            tags: Vec<String>,
+        }
+
+        let config = DeserializerConfig {
+            coerce_scalar_types: true,
+            ..DeserializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<String>(&av_n("42")),
+            Err(Error::new("invalid type: integer `42`, expected a string"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<i64>(&av_s("42")),
+            Err(Error::new("Number Value Expected, Found String"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<&str>(&av_n("42")),
+            Ok("42")
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<String>(&av_n("42"), &config),
+            Ok("42".to_owned())
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<i64>(&av_s("42"), &config),
+            Ok(42)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<i64>(&av_s("a"), &config),
+            Err(Error::new("Numeric Value Expected"))
+        );
+
+        let value = AttributeValue {
+            m: Some(hashmap! {
+                "id".to_owned() => av_n("1.50"),
+                "count".to_owned() => av_s("-7"),
+                "tags".to_owned() => AttributeValue {
+                    ns: Some(vec!["1".to_owned(), "2e3".to_owned()]),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+        let expected = Test {
+            id: "1.50".to_owned(),
+            count: -7,
+            tags: vec!["1".to_owned(), "2e3".to_owned()],
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<Test>(&value, &config),
+            Ok(expected)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned_with_config::<Test>(value, &config),
+            Ok(Test {
+                id: "1.50".to_owned(),
+                count: -7,
+                tags: vec!["1".to_owned(), "2e3".to_owned()],
+            })
+        );
+    }
+
+    // Empty Values
+
+    #[test]
+    fn deserialize_empty_any() {
+        let list = AttributeValue {
+            l: Some(vec![]),
+            ..AttributeValue::default()
+        };
+        let map = AttributeValue {
+            m: Some(HashMap::new()),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<serde_json::Value>(&list),
+            Ok(json!([]))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<serde_json::Value>(&map),
+            Ok(json!({}))
+        );
+    }
+
+    // Set Values
+
+    #[test]
+    fn deserialize_set_types() {
+        let list = AttributeValue {
+            l: Some(vec![AttributeValue {
+                s: Some("a".to_owned()),
+                ..AttributeValue::default()
+            }]),
+            ..AttributeValue::default()
+        };
+        let set = AttributeValue {
+            ss: Some(vec!["a".to_owned()]),
+            ..AttributeValue::default()
+        };
+        let config = DeserializerConfig {
+            strict_set_types: true,
+            ..DeserializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<StringSet<Vec<String>>>(&list),
+            Ok(StringSet(vec!["a".to_owned()]))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<StringSet<Vec<String>>>(
+                &list, &config
+            ),
+            Err(Error::new("String Set Value Expected, Found List"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<StringSet<Vec<String>>>(
+                &set, &config
+            ),
+            Ok(StringSet(vec!["a".to_owned()]))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<std::collections::HashSet<String>>(&set),
+            Ok(std::collections::HashSet::from(["a".to_owned()]))
+        );
+    }
+
+    // Lists (as written for data which is logically a set) are accepted by the
+    // standard set types, removing duplicates, whether or not set types are
+    // strict.
+
+    #[test]
+    fn deserialize_list_into_hash_set() {
+        use std::collections::HashSet;
+
+        let list = AttributeValue {
+            l: Some(
+                ["1", "2", "1", "-3"]
+                    .iter()
+                    .map(|n| AttributeValue {
+                        n: Some(n.to_string()),
+                        ..AttributeValue::default()
+                    })
+                    .collect(),
+            ),
+            ..AttributeValue::default()
+        };
+        let config = DeserializerConfig {
+            strict_set_types: true,
+            ..DeserializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<HashSet<i64>>(&list),
+            Ok(HashSet::from([1, 2, -3]))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<HashSet<i64>>(&list, &config),
+            Ok(HashSet::from([1, 2, -3]))
+        );
+    }
+
+    // Number set elements which are whole numbers written in float form are
+    // normalized to integers where configured, while other numbers are not.
+
+    #[test]
+    fn deserialize_normalized_set_numbers() {
+        use std::collections::HashSet;
+
+        let ns = |ns: &[&str]| AttributeValue {
+            ns: Some(ns.iter().map(|n| n.to_string()).collect()),
+            ..AttributeValue::default()
+        };
+        let config = DeserializerConfig {
+            normalize_set_numbers: true,
+            ..DeserializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<HashSet<i64>>(
+                &ns(&["1", "2.0", "3", "-4.00", "5e1"]),
+                &config
+            ),
+            Ok(HashSet::from([1, 2, 3, -4, 50]))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<HashSet<i64>>(
+                &ns(&["1", "2.5"]),
+                &config
+            ),
+            Err(Error::new("Integer Value Expected, Found 2.5"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<HashSet<u8>>(
+                &ns(&["256.0"]),
+                &config
+            ),
+            Err(Error::new("Number 256.0 Out Of Range For u8"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<HashSet<i64>>(&ns(&["1", "2.0"])),
+            Err(Error::new("Integer Value Expected, Found 2.0"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<i64>(
+                &AttributeValue {
+                    n: Some("2.0".to_owned()),
+                    ..AttributeValue::default()
+                },
+                &config
+            ),
+            Err(Error::new("Integer Value Expected, Found 2.0"))
+        );
+    }
+
+    // Unit Values
+
+    #[test]
+    fn deserialize_unit_struct() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test;
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            }),
+            Ok(Test)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&AttributeValue {
+                s: Some("Test".to_owned()),
+                ..AttributeValue::default()
+            }),
+            Err(Error::new("Null Value Expected For Unit"))
+        );
+    }
+
+    // Option Values
+
+    #[test]
+    fn deserialize_option_absent_field() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            a: Option<i32>,
+            b: Option<i32>,
+        }
+
+        let present = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    n: Some("1".to_owned()),
+                    ..AttributeValue::default()
+                },
+                "b".to_owned() => AttributeValue {
+                    null: Some(true),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+        let absent = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    n: Some("1".to_owned()),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&present),
+            Ok(Test {
+                a: Some(1),
+                b: None
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&absent),
+            Ok(Test {
+                a: Some(1),
+                b: None
+            })
+        );
+    }
+
+    // Absent attributes are simply not visited, and so containers with a
+    // default fill all absent fields from the default of the container.
+
+    #[test]
+    fn deserialize_container_default() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(default)]
+        struct Test {
+            a: i32,
+            b: String,
+            c: Vec<i32>,
+        }
+
+        impl Default for Test {
+            fn default() -> Self {
+                Test {
+                    a: 1,
+                    b: "default".to_owned(),
+                    c: vec![2, 3],
+                }
+            }
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_item::<Test>(&hashmap! {
+                "b".to_owned() => AttributeValue {
+                    s: Some("present".to_owned()),
+                    ..AttributeValue::default()
+                },
+            }),
+            Ok(Test {
+                a: 1,
+                b: "present".to_owned(),
+                c: vec![2, 3],
+            })
+        );
+    }
+
+    // Null values are treated as null whether true (as conventionally written)
+    // or false (as occasionally written by other tools).
+
+    #[test]
+    fn deserialize_null_values() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        enum Test {
+            Unit,
+        }
+
+        for null in [true, false] {
+            let null = AttributeValue {
+                null: Some(null),
+                ..AttributeValue::default()
+            };
+
+            assert_eq!(
+                serde_rusoto_dynamodb::from_attribute_value::<Option<i32>>(&null),
+                Ok(None)
+            );
+            assert_eq!(
+                serde_rusoto_dynamodb::from_attribute_value::<()>(&null),
+                Ok(())
+            );
+            assert_eq!(
+                serde_rusoto_dynamodb::from_attribute_value::<serde_json::Value>(&null),
+                Ok(serde_json::Value::Null)
+            );
+            assert_eq!(
+                serde_rusoto_dynamodb::from_attribute_value::<Test>(&AttributeValue {
+                    m: Some(hashmap! { "Unit".to_owned() => null }),
+                    ..AttributeValue::default()
+                }),
+                Ok(Test::Unit)
+            );
+        }
+    }
+
+    // Numeric Values
+
+    #[test]
+    fn deserialize_display_from_str() {
+        #[serde_with::serde_as]
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            #[serde_as(as = "serde_with::DisplayFromStr")]
+            value: u128,
+        }
+
+        let value = AttributeValue {
+            m: Some(hashmap! {
+                "value".to_owned() => AttributeValue {
+                    n: Some("340282366920938463463374607431768211455".to_owned()),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&value),
+            Ok(Test { value: u128::MAX })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<&str>(&AttributeValue {
+                n: Some("1.50".to_owned()),
+                ..AttributeValue::default()
+            }),
+            Ok("1.50")
+        );
+    }
+
+    #[test]
+    fn deserialize_integer_range() {
+        fn range<T>(n: &AttributeValue) -> Result<T, Error>
+        where
+            T: DeserializeOwned,
+        {
+            serde_rusoto_dynamodb::from_attribute_value::<T>(n)
+        }
+
+        assert_eq!(
+            range::<i8>(&av_n("128")),
+            Err(Error::new("Number 128 Out Of Range For i8"))
+        );
+        assert_eq!(
+            range::<i16>(&av_n("-32769")),
+            Err(Error::new("Number -32769 Out Of Range For i16"))
+        );
+        assert_eq!(
+            range::<i32>(&av_n("2147483648")),
+            Err(Error::new("Number 2147483648 Out Of Range For i32"))
+        );
+        assert_eq!(
+            range::<i64>(&av_n("9223372036854775808")),
+            Err(Error::new(
+                "Number 9223372036854775808 Out Of Range For i64"
+            ))
+        );
+        assert_eq!(
+            range::<u8>(&av_n("300")),
+            Err(Error::new("Number 300 Out Of Range For u8"))
+        );
+        assert_eq!(
+            range::<u16>(&av_n("-1")),
+            Err(Error::new("Number -1 Out Of Range For u16"))
+        );
+        assert_eq!(
+            range::<u32>(&av_n("4294967296")),
+            Err(Error::new("Number 4294967296 Out Of Range For u32"))
+        );
+        assert_eq!(
+            range::<u64>(&av_n("18446744073709551616")),
+            Err(Error::new(
+                "Number 18446744073709551616 Out Of Range For u64"
+            ))
+        );
+        assert_eq!(
+            range::<u8>(&av_n("1.5")),
+            Err(Error::new("Integer Value Expected, Found 1.5"))
+        );
+        assert_eq!(
+            range::<i128>(&av_n("170141183460469231731687303715884105728")),
+            Err(Error::new(
+                "Number 170141183460469231731687303715884105728 Out Of Range For i128"
+            ))
+        );
+        assert_eq!(range::<u64>(&av_n("18446744073709551615")), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn deserialize_lenient_numbers() {
+        let padded = AttributeValue {
+            n: Some(" 42 ".to_owned()),
+            ..AttributeValue::default()
+        };
+        let signed = AttributeValue {
+            n: Some("+42".to_owned()),
+            ..AttributeValue::default()
+        };
+        let config = DeserializerConfig {
+            lenient_numbers: true,
+            ..DeserializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<i64>(&padded, &config),
+            Ok(42)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<f64>(&padded, &config),
+            Ok(42.0)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<u8>(&signed, &config),
+            Ok(42)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<i64>(&padded),
+            Err(Error::new("Numeric Value Expected"))
+        );
+    }
+
+    // Float Values
+
+    #[test]
+    fn deserialize_f32_range() {
+        let in_range = AttributeValue {
+            n: Some("3.25e38".to_owned()),
+            ..AttributeValue::default()
+        };
+        let out_of_range = AttributeValue {
+            n: Some("3.5e38".to_owned()),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<f32>(&in_range),
+            Ok(3.25e38f32)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<f32>(&out_of_range),
+            Err(Error::new("Number 3.5e38 Out Of Range For f32"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<f64>(&out_of_range),
+            Ok(3.5e38f64)
+        );
+    }
+
+    // The shortest form of f32::MAX (3.4028235e38) is slightly greater than
+    // f32::MAX when parsed as an f64, so the range is checked after rounding.
+
+    #[test]
+    fn deserialize_f32_max() {
+        let max = AttributeValue {
+            n: Some("3.4028235e38".to_owned()),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<f32>(&max),
+            Ok(f32::MAX)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<f32>(&AttributeValue {
+                n: Some("-3.4028235e38".to_owned()),
+                ..AttributeValue::default()
+            }),
+            Ok(f32::MIN)
+        );
+    }
+
+    #[test]
+    fn deserialize_f64_range() {
+        let out_of_range = AttributeValue {
+            n: Some("1e309".to_owned()),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<f64>(&out_of_range),
+            Err(Error::new("Number 1e309 Out Of Range For f64"))
+        );
+    }
+
+    // Exact numbers reject floats which do not represent the number value
+    // exactly, while integers always require integer form.
+
+    #[test]
+    fn deserialize_exact_numbers() {
+        let config = DeserializerConfig {
+            exact_numbers: true,
+            ..DeserializerConfig::default()
+        };
+
+        fn exact<T: DeserializeOwned>(
+            value: &AttributeValue,
+            config: &DeserializerConfig,
+        ) -> Result<T, Error> {
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<T>(value, config)
+        }
+
+        assert_eq!(
+            exact::<i64>(&av_n("1.5"), &config),
+            Err(Error::new("Integer Value Expected, Found 1.5"))
+        );
+        assert_eq!(
+            exact::<i64>(&av_n("2.0"), &config),
+            Err(Error::new("Integer Value Expected, Found 2.0"))
+        );
+        assert_eq!(exact::<f64>(&av_n("0.1"), &config), Ok(0.1));
+        assert_eq!(exact::<f64>(&av_n("1.50"), &config), Ok(1.5));
+        assert_eq!(exact::<f64>(&av_n("1e2"), &config), Ok(100.0));
+        assert_eq!(exact::<f32>(&av_n("0.1"), &config), Ok(0.1));
+        assert_eq!(
+            exact::<f64>(&av_n("0.10000000000000000001"), &config),
+            Err(Error::new(
+                "Number 0.10000000000000000001 Can Not Be Represented Exactly As f64"
+            ))
+        );
+        assert_eq!(
+            exact::<f64>(&av_n("9007199254740993"), &config),
+            Err(Error::new(
+                "Number 9007199254740993 Can Not Be Represented Exactly As f64"
+            ))
+        );
+        assert_eq!(
+            exact::<f32>(&av_n("16777217"), &config),
+            Err(Error::new(
+                "Number 16777217 Can Not Be Represented Exactly As f32"
+            ))
+        );
+        assert_eq!(
+            exact::<serde_json::Value>(&av_n("18446744073709551616"), &config),
+            Err(Error::new(
+                "Number 18446744073709551616 Can Not Be Represented Exactly As f64"
+            ))
+        );
+        assert_eq!(
+            exact::<f64>(&av_n("9007199254740993"), &DeserializerConfig::default()),
+            Ok(9007199254740992.0)
+        );
+    }
+
+    // Flattened Values
+
+    #[test]
+    fn deserialize_flatten_catch_all() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Typed {
+            id: String,
+            count: u64,
+            #[serde(flatten)]
+            extra: HashMap<String, serde_json::Value>,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Strings {
+            id: String,
+            count: u64,
+            #[serde(flatten)]
+            extra: HashMap<String, String>,
+        }
+
+        let typed = AttributeValue {
+            m: Some(hashmap! {
+                "id".to_owned() => av_s("abc"),
+                "count".to_owned() => av_n("18446744073709551615"),
+                "name".to_owned() => av_s("x"),
+                "size".to_owned() => av_n("1.5"),
+                "flag".to_owned() => AttributeValue {
+                    bool: Some(true),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+        let strings = AttributeValue {
+            m: Some(hashmap! {
+                "id".to_owned() => av_s("abc"),
+                "count".to_owned() => av_n("1"),
+                "a".to_owned() => av_s("x"),
+                "b".to_owned() => av_s("y"),
+                "c".to_owned() => av_s("z"),
+            }),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Typed>(&typed),
+            Ok(Typed {
+                id: "abc".to_owned(),
+                count: u64::MAX,
+                extra: hashmap! {
+                    "name".to_owned() => json!("x"),
+                    "size".to_owned() => json!(1.5),
+                    "flag".to_owned() => json!(true),
+                },
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Strings>(&strings),
+            Ok(Strings {
+                id: "abc".to_owned(),
+                count: 1,
+                extra: hashmap! {
+                    "a".to_owned() => "x".to_owned(),
+                    "b".to_owned() => "y".to_owned(),
+                    "c".to_owned() => "z".to_owned(),
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_flatten_enum() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        enum Kind {
+            A,
+            B(u32),
+            C { x: String },
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            id: String,
+            #[serde(flatten)]
+            kind: Kind,
+        }
+
+        let test = |kind: Kind| Test {
+            id: "abc".to_owned(),
+            kind,
+        };
+
+        roundtrip(
+            &test(Kind::A),
+            &av_map(hashmap! {
+                "id".to_owned() => av_s("abc"),
+                "A".to_owned() => AttributeValue {
+                    null: Some(true),
+                    ..AttributeValue::default()
+                },
+            }),
+        );
+        roundtrip(
+            &test(Kind::B(1)),
+            &av_map(hashmap! {
+                "id".to_owned() => av_s("abc"),
+                "B".to_owned() => av_n("1"),
+            }),
+        );
+        roundtrip(
+            &test(Kind::C { x: "y".to_owned() }),
+            &av_map(hashmap! {
+                "id".to_owned() => av_s("abc"),
+                "C".to_owned() => av_map(hashmap! { "x".to_owned() => av_s("y") }),
+            }),
+        );
+
+        let missing = av_map(hashmap! { "id".to_owned() => av_s("abc") });
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&missing),
+            Err(Error::new(
+                "no variant of enum Kind found in flattened data"
+            ))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned::<Test>(missing),
+            Err(Error::new(
+                "no variant of enum Kind found in flattened data"
+            ))
+        );
+    }
+
+    // Enum Values
+
+    #[test]
+    fn deserialize_numeric_enum_discriminant() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        enum Test {
+            A,
+            B,
+            C,
+        }
+
+        let value = AttributeValue {
+            n: Some("1".to_owned()),
+            ..AttributeValue::default()
+        };
+        let config = DeserializerConfig {
+            numeric_enum_discriminants: true,
+            ..DeserializerConfig::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<Test>(&value, &config),
+            Ok(Test::B)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&value),
+            Err(Error::new("Map Value Expected"))
+        );
+    }
+
+    #[test]
+    fn deserialize_enum_map_order() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        enum Test {
+            A(usize),
+            B(String),
+        }
+
+        let value = AttributeValue {
+            m: Some(
+                (0..500)
+                    .map(|i| {
+                        let variant = match i % 2 {
+                            0 => hashmap! {
+                                "A".to_owned() => AttributeValue {
+                                    n: Some(i.to_string()),
+                                    ..AttributeValue::default()
+                                }
+                            },
+                            _ => hashmap! {
+                                "B".to_owned() => AttributeValue {
+                                    s: Some(i.to_string()),
+                                    ..AttributeValue::default()
+                                }
+                            },
+                        };
+
+                        (
+                            format!("key{}", i),
+                            AttributeValue {
+                                m: Some(variant),
+                                ..AttributeValue::default()
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            ..AttributeValue::default()
+        };
+
+        let expected: HashMap<String, Test> = (0..500)
+            .map(|i| {
+                let variant = match i % 2 {
+                    0 => Test::A(i),
+                    _ => Test::B(i.to_string()),
+                };
+
+                (format!("key{}", i), variant)
+            })
+            .collect();
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<HashMap<String, Test>>(&value),
+            Ok(expected)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&AttributeValue {
+                m: Some(hashmap! {
+                    "A".to_owned() => AttributeValue {
+                        n: Some("1".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                    "B".to_owned() => AttributeValue {
+                        s: Some("2".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            }),
+            Err(Error::new(
+                "Map Value With A Single Key Expected, Found 2 Keys"
+            ))
+        );
+    }
+
+    #[test]
+    fn deserialize_enum_other() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        enum Test {
+            A,
+            B(i32),
+            #[serde(other)]
+            Unknown,
+        }
+
+        let variant = |key: &str, value: AttributeValue| AttributeValue {
+            m: Some(hashmap! { key.to_owned() => value }),
+            ..AttributeValue::default()
+        };
+
+        let null = AttributeValue {
+            null: Some(true),
+            ..AttributeValue::default()
+        };
+
+        let data = AttributeValue {
+            m: Some(hashmap! {
+                "x".to_owned() => AttributeValue {
+                    n: Some("1".to_owned()),
+                    ..AttributeValue::default()
+                }
+            }),
+            ..AttributeValue::default()
+        };
+
+        let unknown_string = AttributeValue {
+            s: Some("C".to_owned()),
+            ..AttributeValue::default()
+        };
+
+        for value in &[
+            variant("C", null.clone()),
+            variant("D", data.clone()),
+            unknown_string,
+        ] {
+            assert_eq!(
+                serde_rusoto_dynamodb::from_attribute_value::<Test>(value),
+                Ok(Test::Unknown)
+            );
+            assert_eq!(
+                serde_rusoto_dynamodb::from_attribute_value_owned::<Test>(value.clone()),
+                Ok(Test::Unknown)
+            );
+        }
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&variant("A", null)),
+            Ok(Test::A)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&variant("A", data.clone())),
+            Err(Error::new("Null Value Expected"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned::<Test>(variant("A", data)),
+            Err(Error::new("Null Value Expected"))
+        );
+    }
+
+    // Variant names (and field names) are borrowed when deserialized as
+    // identifiers, as they are by derived implementations.
+
+    #[test]
+    fn deserialize_borrowed_identifiers() {
+        use serde::de::{EnumAccess, IgnoredAny, VariantAccess, Visitor};
+        use std::fmt::Formatter;
+
+        #[derive(Eq, Hash, PartialEq)]
+        struct Identifier<'a>(&'a str);
+
+        impl<'de> Deserialize<'de> for Identifier<'de> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct IdentifierVisitor;
+
+                impl<'de> Visitor<'de> for IdentifierVisitor {
+                    type Value = Identifier<'de>;
+
+                    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                        f.write_str("a borrowed identifier")
+                    }
+
+                    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E> {
+                        Ok(Identifier(v))
+                    }
+                }
+
+                deserializer.deserialize_identifier(IdentifierVisitor)
+            }
+        }
+
+        struct Variant<'a>(&'a str);
+
+        impl<'de> Deserialize<'de> for Variant<'de> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct VariantVisitor;
+
+                impl<'de> Visitor<'de> for VariantVisitor {
+                    type Value = Variant<'de>;
+
+                    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                        f.write_str("an enum")
+                    }
+
+                    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: EnumAccess<'de>,
+                    {
+                        let (Identifier(name), variant) = data.variant()?;
+
+                        variant.newtype_variant::<IgnoredAny>()?;
+
+                        Ok(Variant(name))
+                    }
+                }
+
+                deserializer.deserialize_enum("Variant", &[], VariantVisitor)
+            }
+        }
+
+        let value = AttributeValue {
+            m: Some(hashmap! {
+                "Created".to_owned() => AttributeValue {
+                    m: Some(hashmap! {
+                        "id".to_owned() => AttributeValue {
+                            n: Some("1".to_owned()),
+                            ..AttributeValue::default()
+                        },
+                    }),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        let variant = serde_rusoto_dynamodb::from_attribute_value::<Variant>(&value).unwrap();
+        let fields =
+            serde_rusoto_dynamodb::from_attribute_value::<HashMap<Identifier, IgnoredAny>>(
+                &value.m.as_ref().unwrap()["Created"],
+            )
+            .unwrap();
+
+        assert_eq!(variant.0, "Created");
+        assert_eq!(
+            fields.keys().map(|key| key.0).collect::<Vec<_>>(),
+            vec!["id"]
+        );
+    }
+
+    #[test]
+    fn deserialize_reset() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            id: String,
+            tags: Vec<Vec<u32>>,
+        }
+
+        let config = DeserializerConfig {
+            recursion_limit: 3,
+            ..DeserializerConfig::default()
+        };
+
+        let expected: Vec<Test> = (0..100)
+            .map(|i| Test {
+                id: format!("item{}", i),
+                tags: vec![vec![i]],
+            })
+            .collect();
+
+        let values: Vec<AttributeValue> = expected
+            .iter()
+            .map(|test| serde_rusoto_dynamodb::to_attribute_value(test).unwrap())
+            .collect();
+
+        let invalid = AttributeValue {
+            s: Some("invalid".to_owned()),
+            ..AttributeValue::default()
+        };
+
+        let mut deserializer =
+            serde_rusoto_dynamodb::de::AttributeValueDeserializer::new(&invalid, &config);
+
+        assert!(deserializer.deserialize::<Test>().is_err());
+
+        for (value, expected) in values.iter().zip(&expected) {
+            deserializer.reset(value);
+
+            assert_eq!(deserializer.deserialize::<Test>().as_ref(), Ok(expected));
+        }
+    }
+
+    // Seq and Map Values
+
+    // Size hints are exact, allowing targets to preallocate when deserializing
+    // large lists and maps.
+
+    struct SizeHint(Option<usize>);
+
+    impl<'de> Deserialize<'de> for SizeHint {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct SizeHintVisitor;
+
+            impl<'de> serde::de::Visitor<'de> for SizeHintVisitor {
+                type Value = SizeHint;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("a list or map")
+                }
+
+                fn visit_seq<A>(self, seq: A) -> Result<SizeHint, A::Error>
+                where
+                    A: serde::de::SeqAccess<'de>,
+                {
+                    Ok(SizeHint(seq.size_hint()))
+                }
+
+                fn visit_map<A>(self, map: A) -> Result<SizeHint, A::Error>
+                where
+                    A: serde::de::MapAccess<'de>,
+                {
+                    Ok(SizeHint(map.size_hint()))
+                }
+            }
+
+            deserializer.deserialize_any(SizeHintVisitor)
+        }
+    }
+
+    #[test]
+    fn deserialize_seq_and_map_size_hint() {
+        let list = av_list((0..10_000usize).map(av_n));
+        let map = av_map((0..100usize).map(|i| (i.to_string(), av_n(i))));
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<SizeHint>(&list).map(|h| h.0),
+            Ok(Some(10_000))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<SizeHint>(&map).map(|h| h.0),
+            Ok(Some(100))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Vec<usize>>(&list),
+            Ok((0..10_000).collect())
+        );
+    }
+
+    // Recursion Limit
+
+    // Nested lists and maps beyond the configured recursion limit (by default
+    // 32, the maximum depth supported by DynamoDB) are rejected.
+
+    #[test]
+    fn deserialize_recursion_limit() {
+        let nested = |depth: usize| {
+            (0..depth).fold(
+                AttributeValue {
+                    null: Some(true),
+                    ..AttributeValue::default()
+                },
+                |value, i| match i % 2 {
+                    0 => AttributeValue {
+                        l: Some(vec![value]),
+                        ..AttributeValue::default()
+                    },
+                    _ => AttributeValue {
+                        m: Some(hashmap! { "a".to_owned() => value }),
+                        ..AttributeValue::default()
+                    },
+                },
+            )
+        };
+
+        assert!(
+            serde_rusoto_dynamodb::from_attribute_value::<serde_json::Value>(&nested(32)).is_ok()
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<serde_json::Value>(&nested(33)),
+            Err(Error::new("Recursion Limit Exceeded"))
+        );
+        assert!(
+            serde_rusoto_dynamodb::from_attribute_value_with_config::<serde_json::Value>(
+                &nested(33),
+                &DeserializerConfig {
+                    recursion_limit: 64,
+                    ..DeserializerConfig::default()
+                }
+            )
+            .is_ok()
+        );
+    }
+
+    // Struct Fields
+
+    // Renamed, aliased, optional and unknown fields behave as expected when
+    // deserializing struct values from maps.
+
+    #[test]
+    fn deserialize_struct_fields() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(deny_unknown_fields)]
+        struct Test {
+            #[serde(rename = "A")]
+            a: u8,
+            #[serde(alias = "bee")]
+            b: u8,
+            c: Option<u8>,
+        }
+
+        let map = |keys: &[&str]| AttributeValue {
+            m: Some(keys.iter().map(|k| (k.to_string(), av_n("1"))).collect()),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&map(&["A", "b", "c"])),
+            Ok(Test {
+                a: 1,
+                b: 1,
+                c: Some(1)
+            })
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&map(&["A", "bee"])),
+            Ok(Test {
+                a: 1,
+                b: 1,
+                c: None
+            })
+        );
+        assert!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&map(&["A", "b", "x"])).is_err()
+        );
+    }
+
+    // Borrowed Values
+
+    #[test]
+    fn deserialize_borrowed() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test<'a> {
+            a: &'a str,
+            b: &'a [u8],
+        }
+
+        let value = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    s: Some("hello".to_owned()),
+                    ..AttributeValue::default()
+                },
+                "b".to_owned() => AttributeValue {
+                    b: Some(b"world".to_vec().into()),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        let test = serde_rusoto_dynamodb::from_attribute_value::<Test>(&value).unwrap();
+        let source = value.m.as_ref().unwrap();
+
+        assert_eq!(
+            test,
+            Test {
+                a: "hello",
+                b: b"world"
+            }
+        );
+        assert!(std::ptr::eq(
+            test.a.as_ptr(),
+            source["a"].s.as_ref().unwrap().as_ptr()
+        ));
+        assert!(std::ptr::eq(
+            test.b.as_ptr(),
+            source["b"].b.as_ref().unwrap().as_ptr()
+        ));
+    }
+
+    // Borrowed Cow values are only produced where borrowing is requested (with
+    // #[serde(borrow)]), otherwise Serde deserializes Cow<str> as owned. Map
+    // keys are borrowed where the key type permits.
+
+    #[test]
+    fn deserialize_borrowed_cow() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test<'a> {
+            #[serde(borrow)]
+            a: Cow<'a, str>,
+            b: HashMap<&'a str, u8>,
+            c: Cow<'a, str>,
+        }
+
+        let value = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    s: Some("hello".to_owned()),
+                    ..AttributeValue::default()
+                },
+                "b".to_owned() => AttributeValue {
+                    m: Some(hashmap! {
+                        "key".to_owned() => AttributeValue {
+                            n: Some("1".to_owned()),
+                            ..AttributeValue::default()
+                        },
+                    }),
+                    ..AttributeValue::default()
+                },
+                "c".to_owned() => AttributeValue {
+                    s: Some("world".to_owned()),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        let test = serde_rusoto_dynamodb::from_attribute_value::<Test>(&value).unwrap();
+
+        assert!(matches!(test.a, Cow::Borrowed("hello")));
+        assert_eq!(test.b, hashmap! { "key" => 1 });
+        assert!(matches!(test.c, Cow::Owned(_)));
+    }
+
+    // Shared strings (such as Arc<str> and Rc<str>) are deserialized through
+    // an owned string (as Serde does not borrow them), from both borrowed and
+    // owned values.
+
+    #[test]
+    fn deserialize_shared_str() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            a: Arc<str>,
+            b: Rc<str>,
+            c: Box<str>,
+            d: Option<Arc<str>>,
+            e: StringSet<Vec<Arc<str>>>,
+        }
+
+        let value = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => av_s("hello"),
+                "b".to_owned() => av_s("world"),
+                "c".to_owned() => av_s("boxed"),
+                "d".to_owned() => av_s("some"),
+                "e".to_owned() => AttributeValue {
+                    ss: Some(vec!["x".to_owned()]),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        let expected = Test {
+            a: Arc::from("hello"),
+            b: Rc::from("world"),
+            c: Box::from("boxed"),
+            d: Some(Arc::from("some")),
+            e: StringSet(vec![Arc::from("x")]),
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&value).as_ref(),
+            Ok(&expected)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned::<Test>(value),
+            Ok(expected)
+        );
+    }
+
+    // Owned Values
+
+    // Owned values deserialize as borrowed values do, but move strings (and the
+    // contents of maps and lists) out of the value rather than copying them.
+
+    #[test]
+    fn deserialize_owned() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        enum Kind {
+            Tagged { name: String },
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Inner {
+            kind: Kind,
+            tags: StringSet<Vec<String>>,
+            values: Vec<Option<i32>>,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            inner: Inner,
+            name: String,
+            scores: HashMap<String, f64>,
+        }
+
+        let value = av_map(hashmap! {
+            "inner".to_owned() => av_map(hashmap! {
+                "kind".to_owned() => av_map(hashmap! {
+                    "Tagged".to_owned() => av_map(hashmap! {
+                        "name".to_owned() => av_s("tag"),
+                    }),
+                }),
+                "tags".to_owned() => AttributeValue {
+                    ss: Some(vec!["a".to_owned()]),
+                    ..AttributeValue::default()
+                },
+                "values".to_owned() => AttributeValue {
+                    l: Some(vec![
+                        av_n("1"),
+                        AttributeValue {
+                            null: Some(true),
+                            ..AttributeValue::default()
+                        },
+                    ]),
+                    ..AttributeValue::default()
+                },
+            }),
+            "name".to_owned() => av_s("hello"),
+            "scores".to_owned() => av_map(hashmap! {
+                "x".to_owned() => av_n("1.5"),
+            }),
+        });
+
+        let expected = serde_rusoto_dynamodb::from_attribute_value::<Test>(&value).unwrap();
+        let name = value.m.as_ref().unwrap()["name"]
+            .s
+            .as_ref()
+            .unwrap()
+            .as_ptr();
+        let test = serde_rusoto_dynamodb::from_attribute_value_owned::<Test>(value).unwrap();
+
+        assert_eq!(test, expected);
+        assert!(std::ptr::eq(test.name.as_ptr(), name));
+    }
+
+    #[test]
+    fn deserialize_owned_string() {
+        use serde::de::{Unexpected, Visitor};
+        use std::fmt::Formatter;
+
+        struct OwnedOnly(String);
+
+        impl<'de> Deserialize<'de> for OwnedOnly {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct OwnedOnlyVisitor;
+
+                impl<'de> Visitor<'de> for OwnedOnlyVisitor {
+                    type Value = OwnedOnly;
+
+                    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                        f.write_str("an owned string")
+                    }
+
+                    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        Err(E::invalid_type(Unexpected::Str(v), &self))
+                    }
+
+                    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+                        Ok(OwnedOnly(v))
+                    }
+                }
+
+                deserializer.deserialize_string(OwnedOnlyVisitor)
+            }
+        }
+
+        let value = av_s("hello");
+        let ptr = value.s.as_ref().unwrap().as_ptr();
+        let string = serde_rusoto_dynamodb::from_attribute_value_owned::<String>(value).unwrap();
+
+        assert_eq!(string, "hello");
+        assert!(std::ptr::eq(string.as_ptr(), ptr));
+
+        let list = AttributeValue {
+            l: Some(vec![av_s("a"), av_s("b")]),
+            ..AttributeValue::default()
+        };
+        let ptrs = list
+            .l
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|value| value.s.as_ref().unwrap().as_ptr())
+            .collect::<Vec<_>>();
+        let strings =
+            serde_rusoto_dynamodb::from_attribute_value_owned::<Vec<String>>(list).unwrap();
+
+        assert_eq!(strings, vec!["a", "b"]);
+        assert!(strings
+            .iter()
+            .zip(ptrs)
+            .all(|(string, ptr)| std::ptr::eq(string.as_ptr(), ptr)));
+
+        let owned = serde_rusoto_dynamodb::from_attribute_value_owned::<OwnedOnly>(av_s("owned"));
+
+        assert_eq!(owned.unwrap().0, "owned");
+    }
+
+    #[test]
+    fn deserialize_owned_recursion_limit() {
+        let nested = (0..3).fold(
+            AttributeValue {
+                n: Some("1".to_owned()),
+                ..AttributeValue::default()
+            },
+            |value, _| AttributeValue {
+                l: Some(vec![value]),
+                ..AttributeValue::default()
+            },
+        );
+        let config = |recursion_limit| DeserializerConfig {
+            recursion_limit,
+            ..DeserializerConfig::default()
+        };
+
+        assert!(
+            serde_rusoto_dynamodb::from_attribute_value_owned_with_config::<Vec<Vec<Vec<i32>>>>(
+                nested.clone(),
+                &config(3)
+            )
+            .is_ok()
+        );
+        assert!(
+            serde_rusoto_dynamodb::from_attribute_value_owned_with_config::<Vec<Vec<Vec<i32>>>>(
+                nested,
+                &config(2)
+            )
+            .is_err()
+        );
+    }
+
+    // Untyped Values
+
+    #[test]
+    fn deserialize_heterogeneous_list_json() {
+        let value = AttributeValue {
+            l: Some(vec![
+                AttributeValue {
+                    s: Some("hello".to_owned()),
+                    ..AttributeValue::default()
+                },
+                AttributeValue {
+                    n: Some("37".to_owned()),
+                    ..AttributeValue::default()
+                },
+                AttributeValue {
+                    n: Some("1.5".to_owned()),
+                    ..AttributeValue::default()
+                },
+                AttributeValue {
+                    bool: Some(true),
+                    ..AttributeValue::default()
+                },
+                AttributeValue {
+                    m: Some(hashmap! {
+                        "a".to_owned() => AttributeValue {
+                            l: Some(vec![]),
+                            ..AttributeValue::default()
+                        }
+                    }),
+                    ..AttributeValue::default()
+                },
+                AttributeValue {
+                    null: Some(true),
+                    ..AttributeValue::default()
+                },
+            ]),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Vec<serde_json::Value>>(&value),
+            Ok(vec![
+                json!("hello"),
+                json!(37),
+                json!(1.5),
+                json!(true),
+                json!({ "a": [] }),
+                json!(null),
+            ])
+        );
+    }
+
+    // Binary values are visited as (borrowed) bytes when deserializing any (by
+    // default), as with untagged enums, which buffer the value before choosing
+    // a variant (the binary value is not valid UTF-8, and so is not accepted as
+    // text).
+
+    #[test]
+    fn deserialize_any_binary() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(untagged)]
+        enum Attribute {
+            Text(String),
+            Binary(serde_bytes::ByteBuf),
+        }
+
+        let value = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    b: Some(vec![0xff, 0x00].into()),
+                    ..AttributeValue::default()
+                },
+                "b".to_owned() => AttributeValue {
+                    s: Some("world".to_owned()),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<HashMap<String, Attribute>>(&value),
+            Ok(hashmap! {
+                "a".to_owned() => Attribute::Binary(serde_bytes::ByteBuf::from(vec![0xff, 0x00])),
+                "b".to_owned() => Attribute::Text("world".to_owned()),
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_any_binary_representation() {
+        use serde_json::{json, Value};
+
+        let value = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    b: Some(vec![0xff, 0x00].into()),
+                    ..AttributeValue::default()
+                },
+                "b".to_owned() => AttributeValue {
+                    bs: Some(vec![vec![1u8].into()]),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        };
+
+        let any = |binary_representation| {
+            let config = DeserializerConfig {
+                binary_representation,
+                ..DeserializerConfig::default()
+            };
+
+            let borrowed =
+                serde_rusoto_dynamodb::from_attribute_value_with_config::<Value>(&value, &config);
+            let owned = serde_rusoto_dynamodb::from_attribute_value_owned_with_config::<Value>(
+                value.clone(),
+                &config,
+            );
+
+            assert_eq!(borrowed, owned);
+
+            borrowed
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Value>(&value),
+            any(BinaryRepresentation::Bytes)
+        );
+        #[cfg(feature = "base64")]
+        assert_eq!(
+            any(BinaryRepresentation::Base64),
+            Ok(json!({ "a": "/wA=", "b": ["AQ=="] }))
+        );
+        assert_eq!(
+            any(BinaryRepresentation::Sequence),
+            Ok(json!({ "a": [255, 0], "b": [[1]] }))
+        );
+        assert_eq!(
+            any(BinaryRepresentation::Bytes),
+            Err(Error::new(
+                "invalid type: byte array, expected any valid JSON value"
+            ))
+        );
+        assert_eq!(
+            any(BinaryRepresentation::Error),
+            Err(Error::new(
+                "Binary Value Not Supported When Deserializing Any"
+            ))
+        );
+    }
+
+    // Tuple Values
+
+    #[test]
+    fn deserialize_tuple_length_mismatch() {
+        let value = AttributeValue {
+            l: Some(vec![
+                AttributeValue {
+                    s: Some("hello".to_owned()),
+                    ..AttributeValue::default()
+                },
+                AttributeValue {
+                    n: Some("37".to_owned()),
+                    ..AttributeValue::default()
+                },
+            ]),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<(String, i32, bool)>(&value),
+            Err(Error::new(
+                "List Value Of Length 3 Expected, Found Length 2"
+            ))
+        );
+    }
+
+    #[test]
+    fn deserialize_tuple_element_type_mismatch() {
+        let value = AttributeValue {
+            l: Some(vec![
+                AttributeValue {
+                    n: Some("37".to_owned()),
+                    ..AttributeValue::default()
+                },
+                AttributeValue {
+                    s: Some("hello".to_owned()),
+                    ..AttributeValue::default()
+                },
+                AttributeValue {
+                    bool: Some(true),
+                    ..AttributeValue::default()
+                },
+            ]),
+            ..AttributeValue::default()
+        };
+
+        assert!(
+            serde_rusoto_dynamodb::from_attribute_value::<(String, i32, bool)>(&value).is_err()
+        );
+    }
+
+    // Lists and maps found in place of each other are reported as such.
+
+    #[test]
+    fn deserialize_list_map_mismatch() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            values: Vec<i32>,
+        }
+
+        let list = AttributeValue {
+            l: Some(vec![]),
+            ..AttributeValue::default()
+        };
+        let map = AttributeValue {
+            m: Some(HashMap::new()),
+            ..AttributeValue::default()
+        };
+        let test = AttributeValue {
+            m: Some(hashmap! { "values".to_owned() => map.clone() }),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&test),
+            Err(Error::new("List Value Expected, Found Map"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned::<Test>(test),
+            Err(Error::new("List Value Expected, Found Map"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<HashMap<String, i32>>(&list),
+            Err(Error::new("Map Value Expected, Found List"))
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned::<HashMap<String, i32>>(list),
+            Err(Error::new("Map Value Expected, Found List"))
+        );
+    }
+}
+
+// Convert
+
+#[cfg(test)]
+mod convert {
+
+    use super::*;
+    use serde_rusoto_dynamodb::{FromAttributeValue, Serde, ToAttributeValue};
+
+    // Direct conversions give the same results as conversion through Serde.
+
+    fn same<T>(value: T)
+    where
+        T: Debug + DeserializeOwned + FromAttributeValue + PartialEq + Serialize + ToAttributeValue,
+    {
+        let direct = value.to_attribute_value().unwrap();
+
+        assert_eq!(
+            direct,
+            serde_rusoto_dynamodb::to_attribute_value(&value).unwrap()
+        );
+        assert_eq!(T::from_attribute_value(&direct).unwrap(), value);
+    }
+
+    #[test]
+    fn convert_scalars() {
+        same(true);
+        same(i8::MIN);
+        same(i64::MAX);
+        same(i128::MIN);
+        same(u64::MAX);
+        same(u128::MAX);
+        same(1.5f32);
+        same(f32::MAX);
+        same(-0.1f64);
+        same(1e300f64);
+        same(2.0f64);
+        same("hello".to_owned());
+    }
+
+    #[test]
+    fn convert_errors() {
+        for value in [av_n("256"), av_n("1.5"), AttributeValue::default()] {
+            assert_eq!(
+                u8::from_attribute_value(&value),
+                serde_rusoto_dynamodb::from_attribute_value::<u8>(&value)
+            );
+        }
+
+        assert_eq!(
+            String::from_attribute_value(&av_n("1")),
+            Err(Error::new("String Value Expected, Found Number"))
+        );
+        assert_eq!(
+            bool::from_attribute_value(&av_n("1")),
+            Err(Error::new("Boolean Value Expected, Found Number"))
+        );
+    }
+
+    #[test]
+    fn convert_serde() {
+        let value = Serde(vec![Some(1), None]);
+        let converted = value.to_attribute_value().unwrap();
+
+        assert_eq!(
+            converted,
+            serde_rusoto_dynamodb::to_attribute_value(&value.0).unwrap()
+        );
+        assert_eq!(Serde::from_attribute_value(&converted), Ok(value));
+    }
+}
+
+// Util
+
+#[cfg(test)]
+mod util {
+
+    use super::*;
+    use maplit::hashmap;
+    use serde_rusoto_dynamodb::util;
+
+    // Item
+
+    #[test]
+    fn into_and_as_item() {
+        let item = hashmap! {
+            "a".to_owned() => AttributeValue {
+                s: Some("hello".to_owned()),
+                ..AttributeValue::default()
+            },
+        };
+        let map = AttributeValue {
+            m: Some(item.clone()),
+            ..AttributeValue::default()
+        };
+        let list = AttributeValue {
+            l: Some(vec![]),
+            ..AttributeValue::default()
+        };
+
+        assert_eq!(util::as_item(&map), Ok(&item));
+        assert_eq!(util::into_item(map), Ok(item));
+        assert_eq!(
+            util::as_item(&list),
+            Err(Error::new("Map Value Expected, Found List"))
+        );
+        assert_eq!(
+            util::into_item(list),
+            Err(Error::new("Map Value Expected, Found List"))
+        );
+    }
+
+    // Item Size
+
+    // Expected sizes are calculated by hand following the documented DynamoDB
+    // item size rules (names and strings by UTF-8 length, numbers by
+    // significant digits, and an overhead for lists and maps).
+
+    #[test]
+    fn item_size() {
+        let item = hashmap! {
+            "id".to_owned() => AttributeValue {
+                s: Some("abc".to_owned()),
+                ..AttributeValue::default()
+            },
+            "count".to_owned() => AttributeValue {
+                n: Some("12345".to_owned()),
+                ..AttributeValue::default()
+            },
+            "active".to_owned() => AttributeValue {
+                bool: Some(true),
+                ..AttributeValue::default()
+            },
+            "tags".to_owned() => AttributeValue {
+                l: Some(vec![AttributeValue {
+                    s: Some("x".to_owned()),
+                    ..AttributeValue::default()
+                }]),
+                ..AttributeValue::default()
+            },
+            "meta".to_owned() => AttributeValue {
+                m: Some(hashmap! {
+                    "a".to_owned() => AttributeValue {
+                        null: Some(true),
+                        ..AttributeValue::default()
+                    }
+                }),
+                ..AttributeValue::default()
+            },
+        };
+
+        // id: 2 + 3, count: 5 + (3 + 1), active: 6 + 1, tags: 4 + (3 + 1 + 1),
+        // meta: 4 + (3 + 1 + 1 + 1)
+
+        assert_eq!(util::item_size(&item), 5 + 9 + 7 + 9 + 10);
+    }
+
+    #[test]
+    fn attribute_value_size_numbers() {
+        let size = |n: &str| {
+            util::attribute_value_size(&AttributeValue {
+                n: Some(n.to_owned()),
+                ..AttributeValue::default()
+            })
+        };
+
+        assert_eq!(size("0"), 1);
+        assert_eq!(size("7"), 2);
+        assert_eq!(size("100"), 2);
+        assert_eq!(size("-0.0012"), 2);
+        assert_eq!(size("1.5e20"), 2);
+        assert_eq!(size("123456789"), 6);
+    }
+
+    #[test]
+    fn attribute_value_size_sets() {
+        assert_eq!(
+            util::attribute_value_size(&AttributeValue {
+                ss: Some(vec!["ab".to_owned(), "cde".to_owned()]),
+                ..AttributeValue::default()
+            }),
+            5
+        );
+        assert_eq!(
+            util::attribute_value_size(&AttributeValue {
+                ns: Some(vec!["1".to_owned(), "1234".to_owned()]),
+                ..AttributeValue::default()
+            }),
+            5
+        );
+    }
+
+    // Debug Item
+
+    #[test]
+    fn debug_item() {
+        let item = hashmap! {
+            "id".to_owned() => AttributeValue {
+                s: Some("abc".to_owned()),
+                ..AttributeValue::default()
+            },
+            "count".to_owned() => AttributeValue {
+                n: Some("42".to_owned()),
+                ..AttributeValue::default()
+            },
+            "tags".to_owned() => AttributeValue {
+                ss: Some(vec!["a".to_owned(), "b".to_owned()]),
+                ..AttributeValue::default()
+            },
+        };
+
+        assert_eq!(
+            util::debug_item(&item),
+            r#"{ count: N42, id: S"abc", tags: SS[a,b] }"#
+        );
+    }
+
+    #[test]
+    fn debug_item_nested() {
+        let item = hashmap! {
+            "flag".to_owned() => AttributeValue {
+                bool: Some(true),
+                ..AttributeValue::default()
+            },
+            "data".to_owned() => AttributeValue {
+                b: Some(vec![0x0a, 0xff].into()),
+                ..AttributeValue::default()
+            },
+            "meta".to_owned() => AttributeValue {
+                m: Some(hashmap! {
+                    "none".to_owned() => AttributeValue {
+                        null: Some(true),
+                        ..AttributeValue::default()
+                    },
+                    "sizes".to_owned() => AttributeValue {
+                        l: Some(vec![
+                            AttributeValue {
+                                n: Some("1".to_owned()),
+                                ..AttributeValue::default()
+                            },
+                            AttributeValue {
+                                ns: Some(vec!["2".to_owned(), "3".to_owned()]),
+                                ..AttributeValue::default()
+                            },
+                        ]),
+                        ..AttributeValue::default()
+                    },
+                }),
+                ..AttributeValue::default()
+            },
+        };
+
+        assert_eq!(
+            util::debug_item(&item),
+            "{\n  data: B<0aff>,\n  flag: BOOLtrue,\n  meta: M{\n    none: NULL,\n    sizes: L[N1, NS[2,3]],\n  },\n}"
+        );
+    }
+
+    // Validation
+
+    #[test]
+    fn validate_attribute_value() {
+        let valid = av_map(hashmap! {
+            "id".to_owned() => AttributeValue {
+                s: Some("abc".to_owned()),
+                ..AttributeValue::default()
+            },
+            "scores".to_owned() => av_list(vec![av_n("1"), av_n("-2.5e3")]),
+            "tags".to_owned() => AttributeValue {
+                ss: Some(vec!["a".to_owned(), "b".to_owned()]),
+                ..AttributeValue::default()
+            },
+            "none".to_owned() => AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            },
+        });
+
+        assert_eq!(util::validate_attribute_value(&valid), Ok(()));
+
+        let check = |value: AttributeValue, message: &str| {
+            assert_eq!(
+                util::validate_attribute_value(&value),
+                Err(Error::new(message))
+            );
+        };
+
+        check(
+            AttributeValue::default(),
+            "Single Type Expected, Found None",
+        );
+        check(
+            AttributeValue {
+                n: Some("1".to_owned()),
+                s: Some("1".to_owned()),
+                ..AttributeValue::default()
+            },
+            "Single Type Expected, Found Number And String",
+        );
+        check(
+            av_map(hashmap! {
+                "a".to_owned() => av_list(vec![av_n("1"), av_map(hashmap! {
+                    "b".to_owned() => AttributeValue::default(),
+                })]),
+            }),
+            "at a[1].b: Single Type Expected, Found None",
+        );
+        check(
+            av_list(vec![av_n("NaN")]),
+            "at [0]: Number Expected, Found \"NaN\"",
+        );
+        check(
+            AttributeValue {
+                null: Some(false),
+                ..AttributeValue::default()
+            },
+            "Null Value Must Be True",
+        );
+        check(
+            AttributeValue {
+                ss: Some(vec![]),
+                ..AttributeValue::default()
+            },
+            "Set Must Not Be Empty",
+        );
+        check(
+            AttributeValue {
+                bs: Some(vec![vec![1].into(), vec![1].into()]),
+                ..AttributeValue::default()
+            },
+            "Set Must Not Contain Duplicates",
+        );
+        check(
+            AttributeValue {
+                ns: Some(vec!["1".to_owned(), "x".to_owned()]),
+                ..AttributeValue::default()
+            },
+            "at [1]: Number Expected, Found \"x\"",
+        );
+    }
+
+    // Builders
+
+    #[test]
+    fn builders() {
+        assert_eq!(
+            util::av_b(vec![1, 2]),
+            AttributeValue {
+                b: Some(vec![1, 2].into()),
+                ..AttributeValue::default()
+            }
+        );
+        assert_eq!(
+            util::av_bool(true),
+            AttributeValue {
+                bool: Some(true),
+                ..AttributeValue::default()
+            }
+        );
+        assert_eq!(
+            util::av_null(),
+            AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            }
+        );
+        assert_eq!(
+            util::av_s("abc"),
+            AttributeValue {
+                s: Some("abc".to_owned()),
+                ..AttributeValue::default()
+            }
+        );
+        assert_eq!(util::av_n(42), util::av_n("42"));
+        assert_eq!(
+            util::av_n("-1.5"),
+            AttributeValue {
+                n: Some("-1.5".to_owned()),
+                ..AttributeValue::default()
+            }
+        );
+        assert_eq!(
+            util::av_list(vec![util::av_n(1), util::av_null()]),
+            AttributeValue {
+                l: Some(vec![util::av_n(1), util::av_null()]),
+                ..AttributeValue::default()
+            }
+        );
+        assert_eq!(
+            util::av_map(vec![
+                ("id".to_owned(), util::av_s("abc")),
+                ("tags".to_owned(), util::av_list(Vec::new())),
+            ]),
+            AttributeValue {
+                m: Some(hashmap! {
+                    "id".to_owned() => util::av_s("abc"),
+                    "tags".to_owned() => util::av_list(Vec::new()),
+                }),
+                ..AttributeValue::default()
+            }
+        );
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            id: String,
+            scores: Vec<f64>,
+            flag: Option<bool>,
+        }
+
+        let test = Test {
+            id: "abc".to_owned(),
+            scores: vec![1.0, 2.5],
+            flag: None,
+        };
+
+        assert_eq!(
+            serde_rusoto_dynamodb::to_attribute_value(&test),
+            Ok(util::av_map(vec![
+                ("id".to_owned(), util::av_s("abc")),
+                (
+                    "scores".to_owned(),
+                    util::av_list(vec![util::av_n("1.0"), util::av_n("2.5")])
+                ),
+                ("flag".to_owned(), util::av_null()),
+            ]))
+        );
+    }
+}
+
+// Result
+
+#[cfg(test)]
+mod result {
+
+    use super::*;
+
+    // Error Interoperability
+
+    #[test]
+    fn error_into_anyhow() {
+        fn deserialize(value: &AttributeValue) -> anyhow::Result<u8> {
+            Ok(serde_rusoto_dynamodb::from_attribute_value::<u8>(value)?)
+        }
+
+        let error = deserialize(&AttributeValue {
+            s: Some("one".to_owned()),
+            ..AttributeValue::default()
+        })
+        .unwrap_err();
+
+        assert!(error.downcast_ref::<Error>().is_some());
+    }
+
+    #[test]
+    fn error_send_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+        assert_send_sync::<Error>();
+    }
+}
+
+// Prelude
+
+#[cfg(test)]
+mod prelude {
+
+    use maplit::hashmap;
+    use rusoto_dynamodb::AttributeValue;
+    use serde::{Deserialize, Serialize};
+    use serde_rusoto_dynamodb::prelude::*;
+
+    // Items
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Test {
+        id: String,
+        tags: StringSet<Vec<String>>,
+    }
+
+    #[test]
+    fn prelude_items() {
+        let value = Test {
+            id: "a".to_owned(),
+            tags: StringSet(vec!["x".to_owned()]),
+        };
+        let item = hashmap! {
+            "id".to_owned() => AttributeValue {
+                s: Some("a".to_owned()),
+                ..AttributeValue::default()
+            },
+            "tags".to_owned() => AttributeValue {
+                ss: Some(vec!["x".to_owned()]),
+                ..AttributeValue::default()
+            },
+        };
+
+        assert_eq!(to_item(&value), Ok(item.clone()));
+        assert_eq!(from_item::<Test>(&item), Ok(value));
+        assert_eq!(
+            to_item(42),
+            Err(Error::new("Map Value Expected, Found Number"))
+        );
+    }
+}
+
+// Properties
+
+#[cfg(test)]
+mod properties {
+
+    use proptest::prelude::*;
+    use serde_json::{Map, Number, Value};
+
+    // Round-trip
+
+    // Arbitrary JSON values (nulls, booleans, numbers, strings, and nested
+    // lists and maps) round-trip exactly through the AttributeValue
+    // representation. Numbers are generated as integers (signed, and unsigned
+    // beyond the signed range) and finite floats, which deserialize to the
+    // same JSON number, as integers are preferred when deserializing any and
+    // floats are written in a form which is never parsed as an integer. Sets
+    // are not generated, as JSON values never serialize to sets.
+
+    fn number() -> impl Strategy<Value = Number> {
+        prop_oneof![
+            any::<i64>().prop_map(Number::from),
+            (i64::MAX as u64 + 1..=u64::MAX).prop_map(Number::from),
+            any::<f64>().prop_filter_map("finite", Number::from_f64),
+        ]
+    }
+
+    fn value() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::Bool),
+            number().prop_map(Value::Number),
+            any::<String>().prop_map(Value::String),
+        ];
+
+        leaf.prop_recursive(4, 64, 8, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Array),
+                prop::collection::hash_map(any::<String>(), inner, 0..8)
+                    .prop_map(|m| Value::Object(m.into_iter().collect::<Map<_, _>>())),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip_json_values(value in value()) {
+            let serialized = serde_rusoto_dynamodb::to_attribute_value(&value).unwrap();
+            let deserialized =
+                serde_rusoto_dynamodb::from_attribute_value::<Value>(&serialized).unwrap();
+
+            prop_assert_eq!(deserialized, value);
+        }
+    }
+}
+
+// Streams
+
+#[cfg(all(test, feature = "streams"))]
+mod streams {
+
+    use super::*;
+    use maplit::hashmap;
+    use rusoto_dynamodbstreams::AttributeValue as StreamsAttributeValue;
+    use serde_rusoto_dynamodb::streams;
+
+    #[test]
+    fn roundtrip_streams() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Test {
+            a: String,
+            b: Vec<i32>,
+        }
+
+        let test = Test {
+            a: "hello".to_owned(),
+            b: vec![1],
+        };
+        let expected = StreamsAttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => StreamsAttributeValue {
+                    s: Some("hello".to_owned()),
+                    ..StreamsAttributeValue::default()
+                },
+                "b".to_owned() => StreamsAttributeValue {
+                    l: Some(vec![StreamsAttributeValue {
+                        n: Some("1".to_owned()),
+                        ..StreamsAttributeValue::default()
+                    }]),
+                    ..StreamsAttributeValue::default()
+                },
+            }),
+            ..StreamsAttributeValue::default()
+        };
+
+        let serialized = streams::to_attribute_value(&test).unwrap();
+
+        assert_eq!(serialized, expected);
+        assert_eq!(streams::from_attribute_value::<Test>(&serialized), Ok(test));
+        assert_eq!(
+            streams::to_streams(streams::to_dynamodb(expected.clone())),
+            expected
+        );
+    }
+}
+
+// Half Floats
+
+#[cfg(all(test, feature = "half"))]
+mod half_floats {
+
+    use super::*;
+    use half::{bf16, f16};
+    use maplit::hashmap;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Test {
+        #[serde(with = "serde_rusoto_dynamodb::half_floats::f16")]
+        a: f16,
+        #[serde(with = "serde_rusoto_dynamodb::half_floats::bf16")]
+        b: bf16,
+    }
+
+    fn av_test(a: &str, b: &str) -> AttributeValue {
+        AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    n: Some(a.to_owned()),
+                    ..AttributeValue::default()
+                },
+                "b".to_owned() => AttributeValue {
+                    n: Some(b.to_owned()),
+                    ..AttributeValue::default()
+                },
+            }),
+            ..AttributeValue::default()
+        }
+    }
+
+    #[test]
+    fn roundtrip_half_floats() {
+        roundtrip(
+            &Test {
+                a: f16::from_f32(1.5),
+                b: bf16::from_f32(-2.0),
+            },
+            &av_test("1.5", "-2.0"),
+        );
+        roundtrip(
+            &Test {
+                a: f16::MAX,
+                b: bf16::MAX,
+            },
+            &av_test("65504.0", "3.3895314e38"),
+        );
+        roundtrip(
+            &Test {
+                a: f16::MIN_POSITIVE_SUBNORMAL,
+                b: bf16::MIN_POSITIVE_SUBNORMAL,
+            },
+            &av_test("5.9604645e-8", "9.1835e-41"),
+        );
+    }
+
+    #[test]
+    fn deserialize_half_floats_range() {
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&av_test("65536", "1")),
+            Err(Error::new("Number 65536 Out Of Range For f16"))
+        );
+    }
+}
+
+// Byte Buffers
+
+#[cfg(all(test, feature = "bytes"))]
+mod byte_buffers {
+
+    use super::*;
+    use bytes1::{Bytes, BytesMut};
+    use maplit::hashmap;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Test {
+        #[serde(with = "serde_rusoto_dynamodb::byte_buffers::bytes")]
+        a: Bytes,
+        #[serde(with = "serde_rusoto_dynamodb::byte_buffers::bytes_mut")]
+        b: BytesMut,
+    }
+
+    #[test]
+    fn roundtrip_byte_buffers() {
+        let test = Test {
+            a: Bytes::from_static(&[0, 1, 255]),
+            b: BytesMut::from(&[2u8, 3][..]),
+        };
+        let expected = AttributeValue {
+            m: Some(hashmap! {
+                "a".to_owned() => av_b(vec![0, 1, 255]),
+                "b".to_owned() => av_b(vec![2, 3]),
+            }),
+            ..AttributeValue::default()
+        };
+
+        roundtrip(&test, &expected);
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value_owned::<Test>(expected),
+            Ok(test)
+        );
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&AttributeValue {
+                m: Some(hashmap! {
+                    "a".to_owned() => AttributeValue {
+                        s: Some("a".to_owned()),
+                        ..AttributeValue::default()
+                    },
+                    "b".to_owned() => av_b(Vec::new()),
+                }),
+                ..AttributeValue::default()
+            }),
+            Err(Error::new("Byte Vector Value Expected"))
+        );
+    }
+}
+
+// DynamoDB JSON
+
+#[cfg(all(test, feature = "json"))]
+mod json {
+
+    use super::*;
+    use maplit::hashmap;
+    use serde_rusoto_dynamodb::json;
+
+    #[test]
+    fn from_dynamodb_json() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            id: String,
+            count: u32,
+            data: OwnedBytes,
+            tags: StringSet<Vec<String>>,
+            meta: HashMap<String, Option<bool>>,
+            list: Vec<i32>,
+        }
+
+        let item = json::from_dynamodb_json(
+            r#"{
+                "id": { "S": "abc" },
+                "count": { "N": "42" },
+                "data": { "B": "AQI=" },
+                "tags": { "SS": ["a", "b"] },
+                "meta": { "M": { "flag": { "BOOL": true }, "none": { "NULL": true } } },
+                "list": { "L": [{ "N": "1" }, { "N": "2" }] }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_rusoto_dynamodb::from_attribute_value::<Test>(&AttributeValue {
+                m: Some(item),
+                ..AttributeValue::default()
+            }),
+            Ok(Test {
+                id: "abc".to_owned(),
+                count: 42,
+                data: OwnedBytes::from(vec![1u8, 2]),
+                tags: StringSet(vec!["a".to_owned(), "b".to_owned()]),
+                meta: hashmap! {
+                    "flag".to_owned() => Some(true),
+                    "none".to_owned() => None,
+                },
+                list: vec![1, 2],
+            })
+        );
+    }
+
+    #[test]
+    fn from_dynamodb_json_invalid() {
+        assert_eq!(
+            json::from_dynamodb_json(r#"{ "a": { "X": "1" } }"#),
+            Err(Error::new("Unknown Type X at line 1 column 12"))
+        );
+        assert_eq!(
+            json::from_dynamodb_json(r#"{ "a": { "S": "1", "N": "1" } }"#),
+            Err(Error::new(
+                "Typed Value With A Single Type Expected at line 1 column 22"
+            ))
+        );
+    }
+
+    #[test]
+    fn from_dynamodb_json_duplicate_keys() {
+        let duplicated = r#"{ "a": { "S": "x" }, "a": { "S": "y" } }"#;
+
+        assert_eq!(
+            json::from_dynamodb_json(duplicated),
+            Ok(hashmap! {
+                "a".to_owned() => AttributeValue {
+                    s: Some("y".to_owned()),
+                    ..AttributeValue::default()
+                },
+            })
+        );
+        assert_eq!(
+            json::from_dynamodb_json_with_config(
+                duplicated,
+                &DeserializerConfig {
+                    reject_duplicate_keys: true,
+                    ..DeserializerConfig::default()
+                }
+            ),
+            Err(Error::new("Duplicate Key a at line 1 column 24"))
+        );
+    }
+
+    #[test]
+    fn items_from_dynamodb_json_reader() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            id: String,
+            count: u32,
+            tags: StringSet<Vec<String>>,
+        }
+
+        let test = |id: &str, count, tags: &[&str]| Test {
+            id: id.to_owned(),
+            count,
+            tags: StringSet(tags.iter().map(|tag| (*tag).to_owned()).collect()),
+        };
+
+        let read = |json: &[u8]| {
+            json::items_from_dynamodb_json_reader(json).and_then(|items| {
+                items
+                    .into_iter()
+                    .map(|item| serde_rusoto_dynamodb::from_item::<Test>(&item))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+        };
+
+        let expected = vec![test("a", 1, &["x", "y"]), test("b", 2, &["z"])];
+
+        assert_eq!(read(include_bytes!("fixtures/scan.json")), Ok(expected));
+        assert_eq!(
+            read(
+                br#"[
+                    { "id": { "S": "a" }, "count": { "N": "1" }, "tags": { "SS": ["x"] } },
+                    { "id": { "S": "b" }, "count": { "N": "2" }, "tags": { "SS": ["y"] } }
+                ]"#
+            ),
+            Ok(vec![test("a", 1, &["x"]), test("b", 2, &["y"])])
+        );
+        assert_eq!(
+            read(
+                br#"
+                { "id": { "S": "a" }, "count": { "N": "1" }, "tags": { "SS": ["x"] } }
+                { "id": { "S": "b" }, "count": { "N": "2" }, "tags": { "SS": ["y"] } }
+                "#
+            ),
+            Ok(vec![test("a", 1, &["x"]), test("b", 2, &["y"])])
+        );
+        assert_eq!(
+            read(br#"{ "Count": 1, "Items": [{ "id": { "S": "a" }, "count": { "N": "1" }, "tags": { "SS": ["x"] } }] }"#),
+            Ok(vec![test("a", 1, &["x"])])
+        );
+        assert_eq!(
+            read(br#"{ "Count": 0, "ScannedCount": 2 }"#),
+            Err(Error::new(
+                "Typed Value Expected For Count at line 1 column 33"
+            ))
+        );
+        assert_eq!(
+            read(br#"{ "id": "abc", "count": { "N": "1" } }"#),
+            Err(Error::new(
+                "Typed Value Expected For id at line 1 column 38"
+            ))
+        );
+        assert_eq!(read(b""), Ok(Vec::new()));
+        assert_eq!(
+            json::items_from_dynamodb_json_reader(&br#"{ "a": { "S": "x" }, "b": 1 }"#[..]),
+            Err(Error::new(
+                "invalid type: integer `1`, expected a DynamoDB JSON typed value at line 1 column 28"
+            ))
+        );
+    }
+}
+
+// Outputs
+
+#[cfg(all(test, feature = "outputs"))]
+mod outputs {
+
+    use super::*;
+    use maplit::hashmap;
+    use rusoto_dynamodb::{GetItemOutput, QueryOutput};
+    use serde_rusoto_dynamodb::outputs;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Test<'a> {
+        id: &'a str,
+    }
+
+    fn item(id: &str) -> HashMap<String, AttributeValue> {
+        hashmap! {
+            "id".to_owned() => AttributeValue {
+                s: Some(id.to_owned()),
+                ..AttributeValue::default()
+            },
+        }
+    }
+
+    #[test]
+    fn from_get_item_output() {
+        let found = GetItemOutput {
+            item: Some(item("a")),
+            ..GetItemOutput::default()
+        };
+
+        assert_eq!(
+            outputs::from_get_item_output::<Test>(&found),
+            Ok(Some(Test { id: "a" }))
+        );
+        assert_eq!(
+            outputs::from_get_item_output::<Test>(&GetItemOutput::default()),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn from_query_output() {
+        let found = QueryOutput {
+            items: Some(vec![item("a"), item("b")]),
+            ..QueryOutput::default()
+        };
+
+        assert_eq!(
+            outputs::from_query_output::<Test>(&found),
+            Ok(vec![Test { id: "a" }, Test { id: "b" }])
+        );
+        assert_eq!(
+            outputs::from_query_output::<Test>(&QueryOutput::default()),
+            Ok(vec![])
+        );
+    }
 }